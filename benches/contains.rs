@@ -0,0 +1,42 @@
+//! Benchmarks of `contains` on ranges of varying complexity.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use vers_rs::schemes::semver::SemVer;
+use vers_rs::{GenericVersionRange, VersionRange};
+
+/// Build a range of `n` disjoint intervals `>=i.0.0|<i.5.0`, each with a `!=` exclusion.
+fn intervals(n: u64) -> GenericVersionRange<SemVer> {
+    let constraints: Vec<String> = (0..n)
+        .flat_map(|i| [format!(">={}.0.0", i), format!("!={}.2.0", i), format!("<{}.5.0", i)])
+        .collect();
+    format!("vers:npm/{}", constraints.join("|")).parse().unwrap()
+}
+
+fn bench_contains(c: &mut Criterion) {
+    let mut group = c.benchmark_group("contains");
+
+    let simple: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    let version: SemVer = "1.5.0".parse().unwrap();
+    group.bench_function("simple", |b| b.iter(|| simple.contains(black_box(&version))));
+
+    let star: GenericVersionRange<SemVer> = "vers:npm/*".parse().unwrap();
+    group.bench_function("star", |b| b.iter(|| star.contains(black_box(&version))));
+
+    for n in [10, 100, 1000] {
+        let range = intervals(n);
+        // Probe the last interval, the worst case for a linear scan
+        let inside: SemVer = format!("{}.3.0", n - 1).parse().unwrap();
+        let outside: SemVer = format!("{}.7.0", n - 1).parse().unwrap();
+        group.bench_with_input(BenchmarkId::new("inside", n), &inside, |b, v| {
+            b.iter(|| range.contains(black_box(v)))
+        });
+        group.bench_with_input(BenchmarkId::new("outside", n), &outside, |b, v| {
+            b.iter(|| range.contains(black_box(v)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_contains);
+criterion_main!(benches);
@@ -28,16 +28,52 @@ pub enum Comparator {
     Any,
 }
 
-impl fmt::Display for Comparator {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl Comparator {
+    /// Get the symbol of this comparator, e.g. `">="` for `GreaterThanOrEqual`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::Comparator;
+    ///
+    /// assert_eq!(Comparator::GreaterThanOrEqual.as_str(), ">=");
+    /// ```
+    pub const fn as_str(&self) -> &'static str {
         match self {
-            Comparator::Equal => write!(f, "="),
-            Comparator::NotEqual => write!(f, "!="),
-            Comparator::LessThan => write!(f, "<"),
-            Comparator::LessThanOrEqual => write!(f, "<="),
-            Comparator::GreaterThan => write!(f, ">"),
-            Comparator::GreaterThanOrEqual => write!(f, ">="),
-            Comparator::Any => write!(f, "*"),
+            Comparator::Equal => "=",
+            Comparator::NotEqual => "!=",
+            Comparator::LessThan => "<",
+            Comparator::LessThanOrEqual => "<=",
+            Comparator::GreaterThan => ">",
+            Comparator::GreaterThanOrEqual => ">=",
+            Comparator::Any => "*",
         }
     }
+
+    /// Check whether this comparator starts an interval, i.e. is `>` or `>=`.
+    pub const fn is_lower_bound(&self) -> bool {
+        matches!(self, Comparator::GreaterThan | Comparator::GreaterThanOrEqual)
+    }
+
+    /// Check whether this comparator ends an interval, i.e. is `<` or `<=`.
+    pub const fn is_upper_bound(&self) -> bool {
+        matches!(self, Comparator::LessThan | Comparator::LessThanOrEqual)
+    }
+
+    /// Check whether this comparator matches its own constraint version, i.e. is `=`, `>=`
+    /// or `<=`.
+    pub const fn is_inclusive(&self) -> bool {
+        matches!(self, Comparator::Equal | Comparator::GreaterThanOrEqual | Comparator::LessThanOrEqual)
+    }
+
+    /// Check whether this comparator concerns its constraint version alone, i.e. is `=` or `!=`.
+    pub const fn is_exact(&self) -> bool {
+        matches!(self, Comparator::Equal | Comparator::NotEqual)
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
@@ -12,11 +12,101 @@ use std::str::FromStr;
 use percent_encoding::percent_decode_str;
 use crate::{Comparator, VersError};
 
-/// A trait alias for version types that can be used in version constraints and ranges.
-pub trait VT: FromStr + Default + Ord + Clone + Display + Debug {}
+/// A trait for version types that can be used in version constraints and ranges.
+///
+/// Besides the required bounds, it provides hooks for scheme-specific behavior, whose
+/// default implementations suit schemes without such behavior. It is not implemented for
+/// all types with the required bounds, so version types opt in explicitly, with an empty
+/// `impl VT for MyVersion {}` where the defaults suit them.
+///
+/// Version types of a versioning scheme should fail parsing with
+/// `VersError::InvalidVersionFormat`, tagged with the `&'static str` name constant of their
+/// scheme (e.g. `SEMVER_SCHEME`) and carrying the message of the underlying parser.
+///
+/// Their `FromStr` receives versions percent-decoded, which may thus contain characters
+/// that must be encoded in a vers string, such as the comma of a Maven bracket range
+/// written as `%2C`. Types for which such characters are invalid reject them when parsing.
+pub trait VT: FromStr + Default + Ord + Clone + Display + Debug {
+    /// Whether versions of this type have a meaningful order.
+    ///
+    /// Ranges of versions without one only support the `=`, `!=` and `*` comparators, and
+    /// fail validation with `VersError::OrderingUnsupported` on the ordered comparators.
+    const HAS_ORDERING: bool = true;
+
+    /// The versioning schemes whose versions this type implements, or an empty list if it
+    /// suits any scheme.
+    ///
+    /// Parsing a range of this type for another scheme fails with
+    /// `VersError::IncompatibleVersioningSchemes`, as its comparison would silently follow
+    /// the wrong rules.
+    const CANONICAL_SCHEMES: &'static [&'static str] = &[];
+
+    /// Check whether this version is a pre-release.
+    ///
+    /// Unless a range allows pre-releases, it only matches a pre-release version if one of
+    /// its constraints allows it (see `allows_prerelease`).
+    fn is_prerelease(&self) -> bool {
+        false
+    }
+
+    /// Check whether a constraint on this version allows matching the given pre-release.
+    ///
+    /// This follows npm semantics, where a pre-release only matches a range if a constraint
+    /// version with the same release tuple is a pre-release as well.
+    fn allows_prerelease(&self, _prerelease: &Self) -> bool {
+        false
+    }
+
+    /// Check whether this version can be ordered against other versions.
+    ///
+    /// Incomparable versions, such as a `latest` tag, are only matched by `=` and `!=`
+    /// constraints, and cannot be used with the ordered comparators. Unlike `HAS_ORDERING`,
+    /// this applies per version rather than per scheme.
+    fn is_comparable(&self) -> bool {
+        true
+    }
 
-// Blanket implementation for any type that satisfies the bounds
-impl<T> VT for T where T: FromStr + Default + Ord + Clone + Display + Debug {}
+    /// Get the smallest release (i.e. non-pre-release) above this version, or `None` if the
+    /// scheme cannot tell.
+    ///
+    /// This lets `is_empty` detect intervals containing pre-releases only, such as
+    /// `>1.0.0|<1.0.1`, which match no version unless pre-releases are allowed.
+    fn next_release(&self) -> Option<Self> {
+        None
+    }
+
+    /// Get the smallest version above all versions starting with this version, or `None` if
+    /// the scheme does not support wildcards.
+    ///
+    /// When supported, a range constraint `=1.2.*` stands for `>=1.2|<1.3`, with `1.3` being
+    /// the wildcard upper bound of `1.2`.
+    fn wildcard_upper(&self) -> Option<Self> {
+        None
+    }
+
+    /// Get an example of a valid version, e.g. for tests and documentation.
+    fn example() -> Self {
+        Self::default()
+    }
+
+    /// Parse a version written in a range of the given versioning scheme.
+    ///
+    /// This defaults to `FromStr`, and lets version types shared by several schemes accept
+    /// syntax that only some of them allow, e.g. partial versions in "npm" ranges.
+    fn parse_in_scheme(s: &str, _versioning_scheme: &str) -> Result<Self, <Self as FromStr>::Err> {
+        s.parse()
+    }
+
+    /// Get the release a pre-release leads up to, e.g. `1.0.0` for `1.0.0-rc.1`.
+    ///
+    /// Versions that are not pre-releases are returned as-is.
+    fn to_release(&self) -> Self {
+        self.clone()
+    }
+}
+
+/// Opaque string versions, ordered lexicographically.
+impl VT for String {}
 
 /// A single version constraint with a comparator and version.
 ///
@@ -54,10 +144,43 @@ impl<V : VT> VersionConstraint<V> {
         Self { comparator, version }
     }
 
+    /// Check whether a version satisfies this constraint in isolation.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version to check
+    ///
+    /// # Returns
+    ///
+    /// `true` if the version satisfies the comparator of this constraint
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::schemes::semver::SemVer;
+    /// use vers_rs::VersionConstraint;
+    ///
+    /// let constraint: VersionConstraint<SemVer> = VersionConstraint::parse("<2.0.0").unwrap();
+    /// assert!(constraint.matches(&"1.5.0".parse().unwrap()));
+    /// assert!(!constraint.matches(&"2.0.0".parse().unwrap()));
+    /// ```
+    pub fn matches(&self, version: &V) -> bool {
+        match self.comparator {
+            Comparator::Equal => version == &self.version,
+            Comparator::NotEqual => version != &self.version,
+            Comparator::LessThan => version < &self.version,
+            Comparator::LessThanOrEqual => version <= &self.version,
+            Comparator::GreaterThan => version > &self.version,
+            Comparator::GreaterThanOrEqual => version >= &self.version,
+            Comparator::Any => true,
+        }
+    }
+
     /// Parse a version constraint string into a `VersionConstraint`.
     ///
     /// This function parses a string like ">=1.0.0" into a `VersionConstraint`
-    /// with the appropriate comparator and version.
+    /// with the appropriate comparator and version. Whitespace around the comparator
+    /// and the version is ignored.
     ///
     /// # Arguments
     ///
@@ -78,6 +201,20 @@ impl<V : VT> VersionConstraint<V> {
     /// assert_eq!(constraint.version, "1.0.0".parse().unwrap());
     /// ```
     pub fn parse(constraint_str: &str) -> Result<Self, VersError> {
+        Self::parse_with_decoding(constraint_str, true, None)
+    }
+
+    /// Parse a version constraint string, percent-decoding the version only if requested.
+    ///
+    /// Without decoding, the version is passed to the version type as-is. The version is
+    /// parsed for the given versioning scheme (see `VT::parse_in_scheme`), or with the
+    /// version type's `FromStr` without one.
+    pub(crate) fn parse_with_decoding(
+        constraint_str: &str,
+        decode_percent: bool,
+        versioning_scheme: Option<&str>,
+    ) -> Result<Self, VersError> {
+        let constraint_str = constraint_str.trim();
         if constraint_str.is_empty() {
             return Err(VersError::InvalidConstraint("Empty constraint".to_string()));
         }
@@ -89,27 +226,57 @@ impl<V : VT> VersionConstraint<V> {
             });
         }
 
-        let (comparator, version) = if constraint_str.starts_with(">=") {
-            (Comparator::GreaterThanOrEqual, &constraint_str[2..])
-        } else if constraint_str.starts_with("<=") {
-            (Comparator::LessThanOrEqual, &constraint_str[2..])
-        } else if constraint_str.starts_with("!=") {
-            (Comparator::NotEqual, &constraint_str[2..])
-        } else if constraint_str.starts_with('>') {
-            (Comparator::GreaterThan, &constraint_str[1..])
-        } else if constraint_str.starts_with('<') {
-            (Comparator::LessThan, &constraint_str[1..])
+        // A star must stand alone as a full constraint
+        if constraint_str.starts_with('*') {
+            return Err(VersError::InvalidConstraint(format!(
+                "Star must not be combined with a version: {}",
+                constraint_str
+            )));
+        }
+
+        // "<>" is an alias for "!=", and must be checked before "<=" and "<"
+        let (comparator, version) = if let Some(rest) = constraint_str.strip_prefix("<>") {
+            (Comparator::NotEqual, rest)
+        } else if let Some(rest) = constraint_str.strip_prefix(">=") {
+            (Comparator::GreaterThanOrEqual, rest)
+        } else if let Some(rest) = constraint_str.strip_prefix("<=") {
+            (Comparator::LessThanOrEqual, rest)
+        } else if let Some(rest) = constraint_str.strip_prefix("!=") {
+            (Comparator::NotEqual, rest)
+        } else if let Some(rest) = constraint_str.strip_prefix('>') {
+            (Comparator::GreaterThan, rest)
+        } else if let Some(rest) = constraint_str.strip_prefix('<') {
+            (Comparator::LessThan, rest)
+        } else if let Some(rest) = constraint_str.strip_prefix('=') {
+            (Comparator::Equal, rest)
         } else {
             (Comparator::Equal, constraint_str)
         };
 
+        let has_comparator = version.len() < constraint_str.len();
         let version = version.trim();
         if version.is_empty() && comparator != Comparator::Any {
             return Err(VersError::InvalidConstraint("Missing version".to_string()));
         }
 
+        // Comparator characters left after the comparator, as in ">=>=1.0.0", would otherwise
+        // surface as a confusing version parse error
+        if has_comparator && version.starts_with(['<', '>', '=', '!']) {
+            return Err(VersError::InvalidConstraint("Multiple comparators".to_string()));
+        }
+
         // Handle URL percent encoding if needed
-        let version_str = if version.contains('%') {
+        let version_str = if decode_percent && version.contains('%') {
+            // Every "%" must start a complete escape sequence, which the decoder would
+            // otherwise silently keep as-is
+            let bytes = version.as_bytes();
+            let malformed = bytes.iter().enumerate().any(|(i, &b)| {
+                b == b'%' && !(bytes.get(i + 1).is_some_and(u8::is_ascii_hexdigit)
+                    && bytes.get(i + 2).is_some_and(u8::is_ascii_hexdigit))
+            });
+            if malformed {
+                return Err(VersError::InvalidConstraint(format!("Invalid URL encoding: {}", version)));
+            }
             match percent_decode_str(version).decode_utf8() {
                 Ok(decoded) => decoded.to_string(),
                 Err(_) => return Err(VersError::InvalidConstraint(format!("Invalid URL encoding: {}", version))),
@@ -118,7 +285,11 @@ impl<V : VT> VersionConstraint<V> {
             version.to_string()
         };
 
-        let parsed_version = version_str.parse::<V>()
+        let parsed_version = match versioning_scheme {
+            Some(versioning_scheme) => V::parse_in_scheme(&version_str, versioning_scheme),
+            None => version_str.parse::<V>(),
+        };
+        let parsed_version = parsed_version
             .map_err(|_| VersError::InvalidConstraint(format!("Failed to parse version: {}", version_str)))?;
 
         Ok(Self { comparator, version: parsed_version })
@@ -10,7 +10,7 @@ use thiserror::Error;
 ///
 /// This enum represents all the possible errors that can occur when parsing,
 /// validating, or using version range specifiers.
-#[derive(Error, Debug, PartialEq, Eq)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum VersError {
     #[error("Invalid URI scheme, expected 'vers'")]
     InvalidScheme,
@@ -18,12 +18,20 @@ pub enum VersError {
     #[error("Missing versioning scheme")]
     MissingVersioningScheme,
     
+    #[error("Invalid versioning scheme: {0}")]
+    InvalidVersioningScheme(String),
+    
     #[error("Empty version constraints")]
     EmptyConstraints,
     
     #[error("Invalid version constraint: {0}")]
     InvalidConstraint(String),
     
+    /// A single constraint of a range failed to parse. The index counts the pipe-separated
+    /// constraints of the specifier, starting at zero.
+    #[error("Failed to parse constraint {index}: {source}")]
+    ConstraintParseError { index: usize, source: Box<VersError> },
+    
     #[error("Duplicate version: {0}")]
     DuplicateVersion(String),
     
@@ -36,6 +44,84 @@ pub enum VersError {
     #[error("Unsupported versioning scheme: {0}")]
     UnsupportedVersioningScheme(String),
     
+    /// A range of an unsupported versioning scheme was parsed, listing the supported schemes.
+    #[error("Unsupported versioning scheme: {scheme}, supported schemes are: {}", supported.join(", "))]
+    UnsupportedVersioningSchemeWithHint { scheme: String, supported: &'static [&'static str] },
+    
+    /// Membership was checked on a structural range, whose versions are not parsed.
+    #[error("Structural ranges cannot evaluate membership, versions of scheme {0} are not compared")]
+    MembershipUnsupported(String),
+    
     #[error("Invalid version format for scheme {0}: {1}, error was: {2}")]
     InvalidVersionFormat(&'static str, String, String),
-}
\ No newline at end of file
+    
+    #[error("Too many version constraints, at most {0} are allowed")]
+    TooManyConstraints(usize),
+    
+    #[error("Versioning scheme {0} has no version ordering, only \"=\", \"!=\" and \"*\" are supported")]
+    OrderingUnsupported(String),
+    
+    #[error("Invalid package URL: {0}")]
+    InvalidPurl(String),
+    
+    #[error("Serialization error: {0}")]
+    Serialization(String),
+}
+/// Broad classification of a `VersError`, for callers deciding how to handle it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The input is malformed or invalid, and processing can continue with other input
+    Input,
+    /// The input uses a versioning scheme this library does not support
+    UnsupportedScheme,
+    /// An operation failed for reasons unrelated to the input, such as a serialization failure
+    Internal,
+}
+
+impl VersError {
+    /// Classify this error.
+    ///
+    /// # Returns
+    ///
+    /// The `ErrorKind` of this error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::{parse, ErrorKind};
+    ///
+    /// assert_eq!(parse("vers:npm/").unwrap_err().kind(), ErrorKind::Input);
+    /// assert_eq!(parse("vers:foo/1.0").unwrap_err().kind(), ErrorKind::UnsupportedScheme);
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            VersError::InvalidScheme
+            | VersError::MissingVersioningScheme
+            | VersError::InvalidVersioningScheme(_)
+            | VersError::EmptyConstraints
+            | VersError::InvalidConstraint(_)
+            | VersError::ConstraintParseError { .. }
+            | VersError::DuplicateVersion(_)
+            | VersError::InvalidRange(_)
+            | VersError::IncompatibleVersioningSchemes(_, _)
+            | VersError::InvalidVersionFormat(_, _, _)
+            | VersError::TooManyConstraints(_)
+            | VersError::OrderingUnsupported(_)
+            | VersError::InvalidPurl(_) => ErrorKind::Input,
+            VersError::UnsupportedVersioningScheme(_)
+            | VersError::UnsupportedVersioningSchemeWithHint { .. }
+            | VersError::MembershipUnsupported(_) => ErrorKind::UnsupportedScheme,
+            VersError::Serialization(_) => ErrorKind::Internal,
+        }
+    }
+
+    /// Check whether this error is caused by the input alone, so that processing can
+    /// skip the offending entry and continue.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the kind of this error is `ErrorKind::Input`
+    pub fn is_recoverable(&self) -> bool {
+        self.kind() == ErrorKind::Input
+    }
+}
@@ -63,16 +63,22 @@
 pub mod error;
 pub mod comparator;
 pub mod constraint;
+pub mod options;
+pub mod osv;
+pub mod purl;
 pub mod schemes;
 pub mod range;
 
 pub use comparator::Comparator;
 pub use constraint::VersionConstraint;
-pub use error::VersError;
+pub use error::{ErrorKind, VersError};
+pub use options::ParseOptions;
 pub use range::VersionRange;
 pub use range::generic::GenericVersionRange;
 pub use range::dynamic::DynamicVersionRange;
 
+use std::collections::HashSet;
+
 /// Parse a version range specifier string into a `DynamicVersionRange`.
 ///
 /// This function automatically detects the versioning scheme and constructs
@@ -127,6 +133,144 @@ pub fn contains(range: &DynamicVersionRange, version_str: &str) -> Result<bool,
     range.contains(version_str)
 }
 
+/// Check many version strings against many dynamic version ranges.
+///
+/// Each version string is parsed at most once per version type, and the parsed version
+/// is reused for all ranges of that type, which makes this faster than calling `contains`
+/// for every pair.
+///
+/// # Arguments
+///
+/// * `ranges` - The dynamic version ranges to check against
+/// * `versions` - The version strings to check
+///
+/// # Returns
+///
+/// One row per range, holding the result of `contains` for each version in order
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::{contains_matrix, parse};
+///
+/// let ranges = [parse("vers:npm/<2.0.0").unwrap(), parse("vers:npm/>=2.0.0").unwrap()];
+/// let matrix = contains_matrix(&ranges, &["1.0.0", "2.0.0"]);
+/// assert_eq!(matrix[0], vec![Ok(true), Ok(false)]);
+/// assert_eq!(matrix[1], vec![Ok(false), Ok(true)]);
+/// ```
+pub fn contains_matrix(ranges: &[DynamicVersionRange], versions: &[&str]) -> Vec<Vec<Result<bool, VersError>>> {
+    fn check<V: constraint::VT<Err = VersError>>(
+        range: &GenericVersionRange<V>,
+        parsed: &mut Option<Vec<Result<V, VersError>>>,
+        versions: &[&str],
+    ) -> Vec<Result<bool, VersError>> {
+        parsed
+            .get_or_insert_with(|| versions.iter().map(|v| V::parse_in_scheme(v, range.versioning_scheme())).collect())
+            .iter()
+            .map(|version| match version {
+                Ok(version) => range.contains(version),
+                Err(e) => Err(e.clone()),
+            })
+            .collect()
+    }
+
+    // Versions are parsed once per version type, and separately for npm, which accepts partial
+    // versions
+    let mut npm = None;
+    let mut semver = None;
+    let mut numeric = None;
+    let mut conda = None;
+    ranges
+        .iter()
+        .map(|range| match range {
+            DynamicVersionRange::SemVer(range) if range.versioning_scheme() == "npm" => check(range, &mut npm, versions),
+            DynamicVersionRange::SemVer(range) => check(range, &mut semver, versions),
+            DynamicVersionRange::Numeric(range) => check(range, &mut numeric, versions),
+            DynamicVersionRange::Conda(range) => check(range, &mut conda, versions),
+        })
+        .collect()
+}
+
+/// Normalize a version range specifier string into its canonical form.
+///
+/// This parses the string with automatic versioning scheme detection and formats it
+/// again, so that whitespace is removed and constraints are sorted and simplified.
+///
+/// # Arguments
+///
+/// * `s` - The version range specifier string to canonicalize
+///
+/// # Returns
+///
+/// A `Result` containing either the canonical string or an error. Unsupported versioning
+/// schemes fail with `VersError::UnsupportedVersioningSchemeWithHint`, as their versions cannot
+/// be ordered.
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::canonicalize;
+///
+/// assert_eq!(canonicalize("vers:npm/ <2.0.0 | >=1.0.0 ").unwrap(), "vers:npm/>=1.0.0|<2.0.0");
+/// ```
+pub fn canonicalize(s: &str) -> Result<String, VersError> {
+    Ok(parse(s)?.to_string())
+}
+
+/// Normalize many version range specifier strings into their canonical forms.
+///
+/// # Arguments
+///
+/// * `inputs` - The version range specifier strings to canonicalize
+///
+/// # Returns
+///
+/// The result of `canonicalize` for every input, in input order
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::canonicalize_many;
+///
+/// let results = canonicalize_many(&["vers:npm/ 1.0.0 ", "vers:pypi/1.0"]);
+/// assert_eq!(results[0].as_deref(), Ok("vers:npm/1.0.0"));
+/// assert!(results[1].is_err());
+/// ```
+pub fn canonicalize_many(inputs: &[&str]) -> Vec<Result<String, VersError>> {
+    inputs.iter().map(|s| canonicalize(s)).collect()
+}
+
+/// Get the unique canonical forms of many version range specifier strings.
+///
+/// # Arguments
+///
+/// * `inputs` - The version range specifier strings to deduplicate
+///
+/// # Returns
+///
+/// A `Result` containing either the distinct canonical strings in order of their first
+/// occurrence, or the error of the first input that fails to canonicalize
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::dedupe_canonical;
+///
+/// let unique = dedupe_canonical(&["vers:npm/>=1.0.0|<2.0.0", "vers:npm/ <2.0.0 | >=1.0.0 "]).unwrap();
+/// assert_eq!(unique, vec!["vers:npm/>=1.0.0|<2.0.0"]);
+/// ```
+pub fn dedupe_canonical(inputs: &[&str]) -> Result<Vec<String>, VersError> {
+    let mut seen = HashSet::new();
+    let mut unique = Vec::new();
+    for canonical in canonicalize_many(inputs) {
+        let canonical = canonical?;
+        if seen.insert(canonical.clone()) {
+            unique.push(canonical);
+        }
+    }
+    Ok(unique)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -324,7 +468,7 @@ mod tests {
     fn test_dynamic_parse_unsupported() {
         let range: Result<DynamicVersionRange, VersError> = "vers:pypi/>=1.0.0|<2.0.0".parse();
         assert!(range.is_err());
-        assert!(matches!(range.unwrap_err(), VersError::UnsupportedVersioningScheme(_)));
+        assert!(matches!(range.unwrap_err(), VersError::UnsupportedVersioningSchemeWithHint { .. }));
     }
 
     #[test]
@@ -366,4 +510,2383 @@ mod tests {
         assert_eq!(range.versioning_scheme(), "npm");
         assert_eq!(range.constraints().len(), 2);
     }
+
+    #[test]
+    fn test_validate_version() {
+        assert!(schemes::validate("npm", "1.2.3").is_ok());
+        assert!(schemes::validate("semver", "1.0.0-alpha.1+build.5").is_ok());
+
+        let result = schemes::validate("npm", "1.2.x");
+        assert!(matches!(result.unwrap_err(), VersError::InvalidVersionFormat(..)));
+
+        let result = schemes::validate("banana", "1.2.3");
+        assert_eq!(result.unwrap_err(), VersError::UnsupportedVersioningScheme("banana".to_string()));
+    }
+
+    #[test]
+    fn test_supported_schemes() {
+        let supported = schemes::supported_schemes();
+        assert!(supported.contains(&"npm"));
+        assert!(supported.contains(&"semver"));
+        assert!(!supported.contains(&"banana"));
+
+        // Every listed scheme must be accepted by the dynamic parser
+        for scheme in supported {
+            let range = parse(&format!("vers:{}/1.0.0", scheme)).unwrap();
+            assert_eq!(range.versioning_scheme(), *scheme);
+        }
+    }
+
+    #[test]
+    fn test_difference() {
+        let a: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<3.0.0".parse().unwrap();
+        let b: GenericVersionRange<SemVer> = "vers:npm/>=1.5.0|<2.0.0".parse().unwrap();
+        let diff = a.difference(&b).unwrap();
+        assert_eq!(diff.to_string(), "vers:npm/>=1.0.0|<1.5.0|>=2.0.0|<3.0.0");
+
+        let c: GenericVersionRange<SemVer> = "vers:npm/1.2.0".parse().unwrap();
+        let diff = a.difference(&c).unwrap();
+        assert_eq!(diff.to_string(), "vers:npm/>=1.0.0|!=1.2.0|<3.0.0");
+        assert_eq!(diff.to_string().parse::<GenericVersionRange<SemVer>>().unwrap(), diff);
+
+        let diff = b.difference(&a).unwrap();
+        assert!(diff.is_empty());
+        assert!(!diff.contains(&"1.7.0".parse().unwrap()).unwrap());
+
+        let other: GenericVersionRange<SemVer> = "vers:semver/>=1.5.0".parse().unwrap();
+        assert!(matches!(
+            a.difference(&other).unwrap_err(),
+            VersError::IncompatibleVersioningSchemes(..)
+        ));
+    }
+
+    #[test]
+    fn test_newly_covered() {
+        let previous: GenericVersionRange<SemVer> = "vers:npm/<1.5.0".parse().unwrap();
+        let current: GenericVersionRange<SemVer> = "vers:npm/<1.8.0".parse().unwrap();
+
+        let newly = current.newly_covered(&previous).unwrap();
+        assert_eq!(newly.to_string(), "vers:npm/>=1.5.0|<1.8.0");
+        assert!(previous.newly_covered(&previous).unwrap().is_empty());
+
+        assert!(current.covers_newly(&previous, &"1.5.0".parse().unwrap()).unwrap());
+        assert!(current.covers_newly(&previous, &"1.7.9".parse().unwrap()).unwrap());
+        assert!(!current.covers_newly(&previous, &"1.4.0".parse().unwrap()).unwrap());
+        assert!(!current.covers_newly(&previous, &"1.8.0".parse().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_parse_structural_whitespace() {
+        let range: DynamicVersionRange = parse("vers:npm/>= 1.0.0").unwrap();
+        assert_eq!(range.constraints()[0].comparator, Comparator::GreaterThanOrEqual);
+        assert_eq!(range.constraints()[0].version.to_string(), "1.0.0");
+
+        let range: DynamicVersionRange = parse(" vers : npm / >= 1.0.0 |\t< 2.0.0 ").unwrap();
+        assert_eq!(range.versioning_scheme(), "npm");
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<2.0.0");
+    }
+
+    #[test]
+    fn test_parse_percent_encoded_space() {
+        let range: GenericVersionRange<String> = "vers:generic/=a%20b".parse().unwrap();
+        assert_eq!(range.constraints().len(), 1);
+        assert_eq!(range.constraints()[0].comparator, Comparator::Equal);
+        assert_eq!(range.constraints()[0].version.to_string(), "a b");
+    }
+
+    #[test]
+    fn test_display_alternate_encoding() {
+        let range: DynamicVersionRange = parse("vers:npm/>=1.0.0%2Bbuild.1|<2.0.0").unwrap();
+        assert_eq!(format!("{}", range), "vers:npm/>=1.0.0+build.1|<2.0.0");
+        assert_eq!(format!("{:#}", range), "vers:npm/>=1.0.0%2Bbuild.1|<2.0.0");
+
+        // Both forms parse back to the same range
+        assert_eq!(parse(&format!("{}", range)).unwrap(), range);
+        assert_eq!(parse(&format!("{:#}", range)).unwrap(), range);
+
+        // Structural characters are always encoded
+        let range: GenericVersionRange<String> = "vers:generic/a%7Cb".parse().unwrap();
+        assert_eq!(format!("{}", range), "vers:generic/a%7Cb");
+    }
+
+    #[test]
+    fn test_split_intervals() {
+        let range: GenericVersionRange<SemVer> =
+            "vers:npm/>=1.0.0|<2.0.0|!=1.5.0|>=3.0.0|<4.0.0|!=5.0.0".parse().unwrap();
+        let parts: Vec<String> = range.split_intervals().iter().map(|r| r.to_string()).collect();
+        assert_eq!(parts, vec!["vers:npm/>=1.0.0|!=1.5.0|<2.0.0", "vers:npm/>=3.0.0|<4.0.0"]);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/<1.0.0|2.0.0|>3.0.0".parse().unwrap();
+        let parts: Vec<String> = range.split_intervals().iter().map(|r| r.to_string()).collect();
+        assert_eq!(parts, vec!["vers:npm/<1.0.0", "vers:npm/2.0.0", "vers:npm/>3.0.0"]);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/*".parse().unwrap();
+        assert_eq!(range.split_intervals(), vec![range.clone()]);
+    }
+
+    #[test]
+    fn test_dynamic_split_intervals() {
+        let range = parse("vers:npm/>=1.0.0|<2.0.0|>=3.0.0|<4.0.0").unwrap();
+        let parts = range.split_intervals();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0], parse("vers:npm/>=1.0.0|<2.0.0").unwrap());
+        assert_eq!(parts[1], parse("vers:npm/>=3.0.0|<4.0.0").unwrap());
+    }
+
+    #[test]
+    fn test_same_constraints() {
+        let npm: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        let semver: GenericVersionRange<SemVer> = "vers:semver/>=1.0.0|<2.0.0".parse().unwrap();
+        let other: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<3.0.0".parse().unwrap();
+        assert_ne!(npm, semver);
+        assert!(npm.same_constraints(&semver));
+        assert!(!npm.same_constraints(&other));
+    }
+
+    #[test]
+    fn test_dynamic_same_constraints() {
+        let range1: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        let range2: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        let range3: DynamicVersionRange = "vers:semver/>=1.0.0|<2.0.0".parse().unwrap();
+        let range4: DynamicVersionRange = "vers:semver/>=1.0.0".parse().unwrap();
+
+        assert!(range1.same_constraints(&range2));
+        assert!(range1.same_constraints(&range3));
+        assert!(!range3.same_constraints(&range4));
+    }
+
+    #[test]
+    fn test_constraint_star_with_version() {
+        let result = VersionConstraint::<SemVer>::parse("*1.0.0");
+        assert!(matches!(result.unwrap_err(), VersError::InvalidConstraint(_)));
+
+        let result: Result<GenericVersionRange<SemVer>, _> = "vers:npm/*1.0.0".parse();
+        assert!(matches!(
+            result.unwrap_err(),
+            VersError::ConstraintParseError { index: 0, source } if matches!(*source, VersError::InvalidConstraint(_))
+        ));
+    }
+
+    #[test]
+    fn test_constraint_missing_version() {
+        for input in ["=", ">=", "<", "!="] {
+            let result = VersionConstraint::<SemVer>::parse(input);
+            assert_eq!(
+                result.unwrap_err(),
+                VersError::InvalidConstraint("Missing version".to_string()),
+                "input: {}",
+                input,
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_round_trip() {
+        let range = parse("vers:npm/>=1.0.0|<2.0.0|!=1.5.0").unwrap();
+        let json = range.to_json().unwrap();
+        assert_eq!(json, "\"vers:npm/>=1.0.0|!=1.5.0|<2.0.0\"");
+        assert_eq!(DynamicVersionRange::from_json(&json).unwrap(), range);
+
+        let result = DynamicVersionRange::from_json("\"vers:npm/\"");
+        assert!(matches!(result.unwrap_err(), VersError::Serialization(_)));
+
+        let result = DynamicVersionRange::from_json("42");
+        assert!(matches!(result.unwrap_err(), VersError::Serialization(_)));
+    }
+
+    #[test]
+    fn test_constraint_matches() {
+        let v = |s: &str| -> SemVer { s.parse().unwrap() };
+        let c = |s: &str| -> VersionConstraint<SemVer> { VersionConstraint::parse(s).unwrap() };
+
+        assert!(c("1.2.3").matches(&v("1.2.3")));
+        assert!(!c("1.2.3").matches(&v("1.2.4")));
+
+        assert!(!c("!=1.2.3").matches(&v("1.2.3")));
+        assert!(c("!=1.2.3").matches(&v("1.2.4")));
+
+        assert!(c("<1.2.3").matches(&v("1.2.2")));
+        assert!(!c("<1.2.3").matches(&v("1.2.3")));
+        assert!(!c("<1.2.3").matches(&v("1.3.0")));
+
+        assert!(c("<=1.2.3").matches(&v("1.2.2")));
+        assert!(c("<=1.2.3").matches(&v("1.2.3")));
+        assert!(!c("<=1.2.3").matches(&v("1.3.0")));
+
+        assert!(!c(">1.2.3").matches(&v("1.2.2")));
+        assert!(!c(">1.2.3").matches(&v("1.2.3")));
+        assert!(c(">1.2.3").matches(&v("1.3.0")));
+
+        assert!(!c(">=1.2.3").matches(&v("1.2.2")));
+        assert!(c(">=1.2.3").matches(&v("1.2.3")));
+        assert!(c(">=1.2.3").matches(&v("1.3.0")));
+
+        assert!(c("*").matches(&v("0.0.0")));
+        assert!(c("*").matches(&v("99.0.0")));
+    }
+
+    #[test]
+    fn test_exact_match_count() {
+        let range: GenericVersionRange<SemVer> = "vers:npm/=1.0.0|=2.0.0".parse().unwrap();
+        assert!(range.is_finite());
+        assert_eq!(range.exact_match_count(), 2);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0".parse().unwrap();
+        assert!(!range.is_finite());
+        assert_eq!(range.exact_match_count(), 0);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/1.0.0|>=2.0.0".parse().unwrap();
+        assert!(!range.is_finite());
+        assert_eq!(range.exact_match_count(), 0);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/!=1.0.0".parse().unwrap();
+        assert!(!range.is_finite());
+    }
+
+    #[test]
+    fn test_constraint_not_equal_alias() {
+        let constraint = VersionConstraint::<SemVer>::parse("<>1.2.3").unwrap();
+        assert_eq!(constraint.comparator, Comparator::NotEqual);
+        assert_eq!(constraint.version.to_string(), "1.2.3");
+
+        let constraint = VersionConstraint::<SemVer>::parse("<=1.2.3").unwrap();
+        assert_eq!(constraint.comparator, Comparator::LessThanOrEqual);
+        let constraint = VersionConstraint::<SemVer>::parse("<1.2.3").unwrap();
+        assert_eq!(constraint.comparator, Comparator::LessThan);
+
+        // The alias is rendered in canonical form
+        let range = parse("vers:npm/<>1.2.3").unwrap();
+        assert_eq!(range.to_string(), "vers:npm/!=1.2.3");
+    }
+
+    #[test]
+    fn test_dynamic_contains_typed() {
+        let range: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0|!=1.5.0".parse().unwrap();
+        for version in ["0.9.0", "1.0.0", "1.5.0", "1.7.0", "2.0.0"] {
+            let typed: SemVer = version.parse().unwrap();
+            assert_eq!(range.contains_typed(&typed).unwrap(), range.contains(version).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_canonical_string() {
+        let a: GenericVersionRange<SemVer> = "vers:npm/ <2.0.0 | >=1.0.0 ".parse().unwrap();
+        let b: GenericVersionRange<SemVer> = "vers:NPM/>=1.0.0|<2.0.0|!=3.0.0".parse().unwrap();
+        assert_eq!(a.canonical_string(), "vers:npm/>=1.0.0|<2.0.0");
+        assert_eq!(b.canonical_string(), "vers:npm/>=1.0.0|<2.0.0");
+
+        let a: GenericVersionRange<SemVer> = "vers:npm/1.0.0%2Bbuild|!=1.5.0".parse().unwrap();
+        let b: GenericVersionRange<SemVer> = "vers:npm/!=1.5.0|1.0.0+build".parse().unwrap();
+        assert_eq!(a.canonical_string(), b.canonical_string());
+        assert_eq!(a.canonical_string(), "vers:npm/1.0.0+build");
+    }
+
+    #[test]
+    fn test_parse_quoted() {
+        let expected = parse("vers:npm/1.2.3").unwrap();
+        assert_eq!(parse("\"vers:npm/1.2.3\"").unwrap(), expected);
+        assert_eq!(parse("'vers:npm/1.2.3'").unwrap(), expected);
+        assert_eq!(parse(" \"vers:npm/1.2.3\" ").unwrap(), expected);
+
+        let range: GenericVersionRange<SemVer> = "'vers:npm/>=1.0.0|<2.0.0'".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<2.0.0");
+
+        // Unmatched quotes are not stripped
+        assert!(parse("\"vers:npm/1.2.3'").is_err());
+        assert!(parse("vers:npm/1.2.3\"").is_err());
+    }
+
+    #[test]
+    fn test_strict_star_with_empty_constraints() {
+        let strict = ParseOptions { strict: true, ..Default::default() };
+        for (input, index) in [("vers:npm/*||", 1), ("vers:npm/|*", 0), ("vers:npm/*|", 1)] {
+            // Lenient parsing ignores the empty constraints
+            let range = parse(input).unwrap();
+            assert_eq!(range.to_string(), "vers:npm/*");
+
+            let result = DynamicVersionRange::from_str_with_options(input, &strict);
+            assert_eq!(
+                result.unwrap_err(),
+                VersError::ConstraintParseError {
+                    index,
+                    source: Box::new(VersError::InvalidConstraint("Empty constraint".to_string())),
+                },
+                "input: {}",
+                input,
+            );
+        }
+
+        let result = DynamicVersionRange::from_str_with_options("vers:npm/>=1.0.0||<2.0.0", &strict);
+        assert!(matches!(result.unwrap_err(), VersError::ConstraintParseError { index: 1, .. }));
+
+        let range = DynamicVersionRange::from_str_with_options("vers:npm/*", &strict).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/*");
+    }
+
+    #[test]
+    fn test_malformed_percent_encoding() {
+        for version in ["%", "%2", "%zz", "1.0.0%", "1.0.0%2", "%FF"] {
+            let result = VersionConstraint::<SemVer>::parse(version);
+            assert!(matches!(result.unwrap_err(), VersError::InvalidConstraint(_)), "version: {}", version);
+
+            // Versions without format restrictions must not accept malformed sequences either
+            let result = VersionConstraint::<String>::parse(version);
+            assert!(matches!(result.unwrap_err(), VersError::InvalidConstraint(_)), "version: {}", version);
+        }
+
+        // A doubly-encoded sequence is only decoded once
+        let constraint = VersionConstraint::<String>::parse("a%2520b").unwrap();
+        assert_eq!(constraint.version, "a%20b");
+    }
+
+    #[test]
+    fn test_why_not() {
+        use crate::range::ExclusionReason;
+
+        let range: GenericVersionRange<SemVer> =
+            "vers:npm/>=1.0.0|<2.0.0|!=1.5.0|>=3.0.0|<4.0.0".parse().unwrap();
+        let why_not = |v: &str| range.why_not(&v.parse().unwrap());
+
+        assert_eq!(why_not("0.9.0"), Some(ExclusionReason::BelowLowerBound));
+        assert_eq!(why_not("4.0.0"), Some(ExclusionReason::AboveUpperBound));
+        assert_eq!(why_not("2.0.0"), Some(ExclusionReason::InGap));
+        assert_eq!(why_not("2.5.0"), Some(ExclusionReason::InGap));
+        assert_eq!(why_not("1.5.0"), Some(ExclusionReason::ExplicitlyExcluded));
+        assert_eq!(why_not("1.0.0"), None);
+        assert_eq!(why_not("3.5.0"), None);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/!=1.0.0".parse().unwrap();
+        assert_eq!(range.why_not(&"1.0.0".parse().unwrap()), Some(ExclusionReason::ExplicitlyExcluded));
+        assert_eq!(range.why_not(&"2.0.0".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_prerelease_policy_consistency() {
+        use crate::range::ExclusionReason;
+        use std::ops::Bound;
+
+        let v = |s: &str| -> SemVer { s.parse().unwrap() };
+        let mut range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0-rc.1|<2.0.0|>=3.0.0".parse().unwrap();
+        let versions = ["0.9.0-beta", "1.0.0-rc.1", "1.0.0-rc.2", "1.5.0-beta", "2.0.0-rc.1", "3.5.0-beta", "1.5.0"];
+        for allow in [false, true] {
+            range.allow_prereleases(allow);
+            for version in versions.iter().map(|s| v(s)) {
+                assert_eq!(
+                    range.why_not(&version).is_none(),
+                    range.contains(&version).unwrap(),
+                    "{} in {} (allowing pre-releases: {})",
+                    version,
+                    range,
+                    allow,
+                );
+            }
+        }
+
+        range.allow_prereleases(false);
+        assert_eq!(range.why_not(&v("1.5.0-beta")), Some(ExclusionReason::PrereleaseNotAllowed));
+        assert_eq!(range.why_not(&v("1.0.0-rc.2")), None);
+        assert_eq!(range.nearest_boundary(&v("1.5.0-beta")), None);
+        assert!(range.nearest_boundary(&v("1.0.0-rc.2")).is_some());
+
+        let (a, b, beta) = (v("1.0.0"), v("1.5.0"), v("1.5.0-beta"));
+        assert!(range.contains_interval(Bound::Included(&a), Bound::Excluded(&b)).unwrap());
+        assert!(!range.contains_interval(Bound::Included(&a), Bound::Included(&beta)).unwrap());
+        range.allow_prereleases(true);
+        assert!(range.contains_interval(Bound::Included(&a), Bound::Included(&beta)).unwrap());
+    }
+
+    #[test]
+    fn test_from_osv_events() {
+        let range = GenericVersionRange::<SemVer>::from_osv_events(
+            "npm", Some("1.0.0"), Some("1.5.0"), None,
+        ).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<1.5.0");
+
+        let range = GenericVersionRange::<SemVer>::from_osv_events(
+            "npm", Some("1.0.0"), None, Some("1.4.2"),
+        ).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<=1.4.2");
+
+        let range = GenericVersionRange::<SemVer>::from_osv_events("npm", Some("0"), Some("1.5.0"), None).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/<1.5.0");
+        let range = GenericVersionRange::<SemVer>::from_osv_events("npm", None, Some("1.5.0"), None).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/<1.5.0");
+
+        let range = GenericVersionRange::<SemVer>::from_osv_events("npm", Some("1.0.0"), None, None).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0");
+        let range = GenericVersionRange::<SemVer>::from_osv_events("npm", None, None, None).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/*");
+
+        let result = GenericVersionRange::<SemVer>::from_osv_events(
+            "npm", Some("1.0.0"), Some("1.5.0"), Some("1.4.0"),
+        );
+        assert!(matches!(result.unwrap_err(), VersError::InvalidRange(_)));
+
+        let result = GenericVersionRange::<SemVer>::from_osv_events("npm", Some("2.0.0"), Some("1.0.0"), None);
+        assert!(matches!(result.unwrap_err(), VersError::InvalidRange(_)));
+        let result = GenericVersionRange::<SemVer>::from_osv_events("npm", Some("1.0.0"), Some("1.0.0"), None);
+        assert!(matches!(result.unwrap_err(), VersError::InvalidRange(_)));
+
+        let result = GenericVersionRange::<SemVer>::from_osv_events("npm", Some("garbage"), None, None);
+        assert!(matches!(result.unwrap_err(), VersError::InvalidVersionFormat(..)));
+    }
+
+    #[test]
+    fn test_to_osv_events() {
+        use crate::osv::OsvEvent::*;
+
+        let range: GenericVersionRange<SemVer> =
+            "vers:npm/>=1.0.0|<1.5.0|>=2.0.0|<2.3.0".parse().unwrap();
+        assert_eq!(range.to_osv_events(), vec![
+            Introduced("1.0.0".to_string()),
+            Fixed("1.5.0".to_string()),
+            Introduced("2.0.0".to_string()),
+            Fixed("2.3.0".to_string()),
+        ]);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/<=1.4.0|3.0.0|>=4.0.0".parse().unwrap();
+        assert_eq!(range.to_osv_events(), vec![
+            Introduced("0".to_string()),
+            LastAffected("1.4.0".to_string()),
+            Introduced("3.0.0".to_string()),
+            LastAffected("3.0.0".to_string()),
+            Introduced("4.0.0".to_string()),
+        ]);
+
+        // Exclusive lower bounds start at the next version, so exclusions stay excluded
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|!=1.5.0|<2.0.0".parse().unwrap();
+        assert_eq!(range.to_osv_events(), vec![
+            Introduced("1.0.0".to_string()),
+            Fixed("1.5.0".to_string()),
+            Introduced("1.5.1-0".to_string()),
+            Fixed("2.0.0".to_string()),
+        ]);
+        let range: GenericVersionRange<SemVer> = "vers:npm/>1.0.0-rc.1".parse().unwrap();
+        assert_eq!(range.to_osv_events(), vec![Introduced("1.0.0-rc.1.0".to_string())]);
+
+        // Round-trip through OSV events
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<1.5.0".parse().unwrap();
+        let events = range.to_osv_events();
+        let (Introduced(introduced), Fixed(fixed)) = (&events[0], &events[1]) else {
+            panic!("unexpected events: {:?}", events);
+        };
+        let parsed = GenericVersionRange::<SemVer>::from_osv_events(
+            "npm", Some(introduced), Some(fixed), None,
+        ).unwrap();
+        assert_eq!(parsed, range);
+    }
+
+    #[test]
+    fn test_allow_prereleases() {
+        let mut range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        let beta: SemVer = "1.5.0-beta".parse().unwrap();
+        assert!(!range.allows_prereleases());
+        assert!(!range.contains(&beta).unwrap());
+        assert!(range.contains(&"1.5.0".parse().unwrap()).unwrap());
+
+        range.allow_prereleases(true);
+        assert!(range.allows_prereleases());
+        assert!(range.contains(&beta).unwrap());
+        assert!(range.contains(&"2.0.0-rc.1".parse().unwrap()).unwrap());
+
+        range.allow_prereleases(false);
+        assert!(!range.contains(&beta).unwrap());
+
+        // A pre-release bound allows pre-releases of the same release tuple
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0-rc.1|<2.0.0".parse().unwrap();
+        assert!(range.contains(&"1.0.0-rc.2".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"1.0.0-alpha".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"1.5.0-beta".parse().unwrap()).unwrap());
+
+        let range: DynamicVersionRange = parse("vers:npm/*").unwrap();
+        assert!(!range.contains("1.0.0-beta").unwrap());
+    }
+
+    #[test]
+    fn test_numeric_scheme() {
+        use crate::schemes::numeric::NumericVersion;
+
+        let v = |s: &str| -> NumericVersion { s.parse().unwrap() };
+        assert_eq!(v("1.2"), v("1.2.0"));
+        assert_eq!(v("1.2.0.0"), v("1.2"));
+        assert!(v("1.2") < v("1.2.1"));
+        assert!(v("12.345.6789") > v("12.345.678"));
+        assert!(v("2") > v("1.99.99"));
+        assert_eq!(v("1.2").to_string(), "1.2");
+
+        for invalid in ["1.a", "1..2", "-1", "+1", "", "1.2-beta"] {
+            let result = invalid.parse::<NumericVersion>();
+            assert!(
+                matches!(result.unwrap_err(), VersError::InvalidVersionFormat("numeric", ..)),
+                "version: {}",
+                invalid,
+            );
+        }
+
+        let range = parse("vers:numeric/>=1.2|<12.345.6789").unwrap();
+        assert!(matches!(range, DynamicVersionRange::Numeric(_)));
+        assert!(range.contains("1.2.0").unwrap());
+        assert!(range.contains("12.345.6788.9999").unwrap());
+        assert!(!range.contains("12.345.6789.0").unwrap());
+        assert!(!range.contains("1.1.99").unwrap());
+        assert!(matches!(range.contains("1.x").unwrap_err(), VersError::InvalidVersionFormat(..)));
+
+        // Zero-padded versions are duplicates
+        let result = parse("vers:numeric/1.2|1.2.0");
+        assert!(matches!(result.unwrap_err(), VersError::DuplicateVersion(_)));
+
+        assert!(schemes::validate("numeric", "1.2.3.4.5").is_ok());
+        assert!(range.contains_typed(&"1.5.0".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_is_disjoint() {
+        let range = |s: &str| -> GenericVersionRange<SemVer> { s.parse().unwrap() };
+
+        assert!(range("vers:npm/<2.0.0").is_disjoint(&range("vers:npm/>=2.0.0")).unwrap());
+        assert!(!range("vers:npm/<=2.0.0").is_disjoint(&range("vers:npm/>=2.0.0")).unwrap());
+        assert!(!range("vers:npm/>=1.0.0|<3.0.0").is_disjoint(&range("vers:npm/>=2.0.0|<4.0.0")).unwrap());
+        assert!(range("vers:npm/>=1.0.0|<2.0.0|>=3.0.0").is_disjoint(&range("vers:npm/>=2.0.0|<3.0.0")).unwrap());
+        assert!(range("vers:npm/>=1.0.0|!=1.5.0|<2.0.0").is_disjoint(&range("vers:npm/1.5.0")).unwrap());
+        assert!(!range("vers:npm/*").is_disjoint(&range("vers:npm/1.5.0")).unwrap());
+
+        let result = range("vers:npm/*").is_disjoint(&range("vers:semver/*"));
+        assert!(matches!(result.unwrap_err(), VersError::IncompatibleVersioningSchemes(..)));
+    }
+
+    #[test]
+    fn test_dynamic_is_disjoint() {
+        let a = parse("vers:npm/<2.0.0").unwrap();
+        assert!(a.is_disjoint(&parse("vers:npm/>=2.0.0").unwrap()).unwrap());
+        assert!(!a.is_disjoint(&parse("vers:npm/>=1.0.0").unwrap()).unwrap());
+
+        let result = a.is_disjoint(&parse("vers:numeric/>=2.0").unwrap());
+        assert!(matches!(result.unwrap_err(), VersError::IncompatibleVersioningSchemes(..)));
+    }
+
+    #[test]
+    fn test_unsupported_scheme_message() {
+        let error = parse("vers:banana/1.0.0").unwrap_err();
+        assert_eq!(
+            error,
+            VersError::UnsupportedVersioningSchemeWithHint {
+                scheme: "banana".to_string(),
+                supported: schemes::supported_schemes(),
+            }
+        );
+
+        let message = error.to_string();
+        assert!(message.contains("banana"));
+        assert!(message.contains("npm"));
+        assert!(message.contains("semver"));
+
+        // Errors outside of parsing ranges only name the scheme
+        assert_eq!(
+            schemes::validate("banana", "1.0.0").unwrap_err().to_string(),
+            "Unsupported versioning scheme: banana"
+        );
+    }
+
+    #[test]
+    fn test_semver_increment_decrement() {
+        let v = |s: &str| -> SemVer { s.parse().unwrap() };
+
+        assert_eq!(v("1.2.3").increment_patch(), v("1.2.4"));
+        assert_eq!(v("0.0.0").increment_patch(), v("0.0.1"));
+        assert_eq!(v("1.2.3-beta+build").increment_patch(), v("1.2.4"));
+        assert_eq!(v("1.2.18446744073709551615").increment_patch(), v("1.2.18446744073709551615"));
+
+        assert_eq!(v("1.2.3").decrement(), Some(v("1.2.2")));
+        assert_eq!(v("0.0.1").decrement(), Some(v("0.0.0")));
+        assert_eq!(v("1.2.3-beta").decrement(), Some(v("1.2.2")));
+
+        // Without a finite predecessor
+        assert_eq!(v("1.2.0").decrement(), None);
+        assert_eq!(v("1.0.0").decrement(), None);
+        assert_eq!(v("0.0.0").decrement(), None);
+        assert_eq!(v("1.2.0-beta").decrement(), None);
+
+        assert_eq!(v("1.2.3").increment_patch().decrement(), Some(v("1.2.3")));
+        assert_eq!(v("1.2.3").decrement().map(|v| v.increment_patch()), Some(v("1.2.3")));
+    }
+
+    #[test]
+    fn test_boundaries() {
+        use std::ops::Bound;
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0|!=1.5.0".parse().unwrap();
+        let boundaries: Vec<(Bound<String>, String)> = range
+            .boundaries()
+            .map(|(bound, version)| (bound.map(|v| v.to_string()), version.to_string()))
+            .collect();
+        assert_eq!(boundaries, vec![
+            (Bound::Included("1.0.0".to_string()), "1.0.0".to_string()),
+            (Bound::Excluded("1.5.0".to_string()), "1.5.0".to_string()),
+            (Bound::Excluded("2.0.0".to_string()), "2.0.0".to_string()),
+        ]);
+
+        let dynamic = parse("vers:npm/>=1.0.0|<2.0.0|!=1.5.0").unwrap();
+        assert_eq!(dynamic.boundaries(), boundaries);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/*".parse().unwrap();
+        assert_eq!(range.boundaries().count(), 0);
+    }
+
+    #[test]
+    fn test_semver_partial_versions() {
+        assert_eq!(SemVer::parse_partial("1").unwrap().to_string(), "1.0.0");
+        assert_eq!(SemVer::parse_partial("1.2").unwrap().to_string(), "1.2.0");
+        assert_eq!(SemVer::parse_partial("1.2.3").unwrap().to_string(), "1.2.3");
+        assert_eq!(SemVer::parse_partial("1.2-beta.1").unwrap().to_string(), "1.2.0-beta.1");
+        for invalid in ["", "1.", "1.2.3.4", "a.b", "1.2.x"] {
+            assert!(SemVer::parse_partial(invalid).is_err(), "{} should be invalid", invalid);
+        }
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.2|<2".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.2.0|<2.0.0");
+        assert!(range.contains(&"1.2.0".parse().unwrap()).unwrap());
+        assert!(range.contains(&"1.9.9".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"1.1.9".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"2.0.0".parse().unwrap()).unwrap());
+
+        assert!(contains(&parse("vers:npm/>=1.2").unwrap(), "1.2.0").unwrap());
+        assert!(schemes::validate("npm", "1.2").is_ok());
+
+        // The "semver" scheme and FromStr require full versions
+        assert!("1.2".parse::<SemVer>().is_err());
+        assert!(schemes::validate("semver", "1.2").is_err());
+        assert!("vers:semver/1.2".parse::<GenericVersionRange<SemVer>>().is_err());
+        assert!(parse("vers:semver/>=1.2").is_err());
+        assert!(parse("vers:semver/1.2.*").is_err());
+        assert!(parse("vers:semver/>=1.2.0").unwrap().contains("1.2").is_err());
+    }
+
+    #[test]
+    fn test_contains_never_panics() {
+        let v = |s: &str| s.parse::<SemVer>().unwrap();
+        let c = |comparator, s: &str| VersionConstraint::new(comparator, v(s));
+
+        let shapes: Vec<Vec<VersionConstraint<SemVer>>> = vec![
+            vec![],
+            vec![VersionConstraint::new(Comparator::Any, SemVer::default())],
+            vec![c(Comparator::LessThan, "1.0.0")],
+            vec![c(Comparator::LessThanOrEqual, "1.0.0")],
+            vec![c(Comparator::GreaterThan, "1.0.0")],
+            vec![c(Comparator::GreaterThanOrEqual, "1.0.0")],
+            vec![c(Comparator::Equal, "1.0.0")],
+            vec![c(Comparator::NotEqual, "1.0.0")],
+            vec![c(Comparator::NotEqual, "1.0.0"), c(Comparator::NotEqual, "2.0.0")],
+            vec![c(Comparator::LessThan, "1.0.0"), c(Comparator::GreaterThan, "2.0.0")],
+            vec![c(Comparator::GreaterThan, "1.0.0"), c(Comparator::LessThan, "2.0.0")],
+            vec![c(Comparator::GreaterThan, "1.0.0"), c(Comparator::GreaterThan, "2.0.0")],
+            vec![c(Comparator::LessThan, "1.0.0"), c(Comparator::LessThan, "2.0.0")],
+            vec![c(Comparator::Equal, "1.0.0"), c(Comparator::NotEqual, "2.0.0"), c(Comparator::LessThan, "3.0.0")],
+            vec![c(Comparator::GreaterThanOrEqual, "1.0.0"), c(Comparator::NotEqual, "1.5.0"), c(Comparator::LessThanOrEqual, "2.0.0")],
+            // Unsorted and unvalidated constraint lists
+            vec![c(Comparator::LessThan, "2.0.0"), c(Comparator::GreaterThan, "1.0.0")],
+            vec![c(Comparator::Equal, "1.0.0"), c(Comparator::Equal, "1.0.0")],
+            vec![
+                VersionConstraint::new(Comparator::Any, SemVer::default()),
+                c(Comparator::NotEqual, "1.0.0"),
+            ],
+        ];
+        let probes = ["0.0.0", "0.9.0", "1.0.0", "1.0.0-rc.1", "1.5.0", "2.0.0", "2.5.0", "3.0.0", "10.0.0"];
+
+        for constraints in shapes {
+            for allow_prereleases in [false, true] {
+                let mut range = GenericVersionRange::new("npm".to_string(), constraints.clone());
+                range.allow_prereleases(allow_prereleases);
+                for probe in probes {
+                    assert!(range.contains(&v(probe)).is_ok(), "{:?} on {:?}", probe, range.constraints);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_range_eq_str() {
+        assert!(parse("vers:npm/ >=1.0.0 ").unwrap() == "vers:npm/>=1.0.0");
+        assert_eq!(parse("vers:npm/<2.0.0|>=1.0.0").unwrap(), "vers:npm/ >=1.0.0 | <2.0.0");
+        assert_eq!(parse("vers:numeric/1.2").unwrap(), "vers:numeric/1.2.0");
+        assert_ne!(parse("vers:npm/>=1.0.0").unwrap(), "vers:semver/>=1.0.0");
+        assert_ne!(parse("vers:npm/>=1.0.0").unwrap(), "vers:npm/>1.0.0");
+        assert_ne!(parse("vers:npm/>=1.0.0").unwrap(), "not a range");
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/1.2.3".parse().unwrap();
+        assert!(range == *"vers:npm/1.2.3");
+        assert_eq!(range, "vers:npm/%31.2.3");
+    }
+
+    #[test]
+    fn test_max_constraints() {
+        let input = format!(
+            "vers:npm/{}",
+            (0..100).map(|i| format!("!={}.0.0", i)).collect::<Vec<_>>().join("|")
+        );
+        let options = ParseOptions { max_constraints: Some(10), ..Default::default() };
+        assert_eq!(
+            DynamicVersionRange::from_str_with_options(&input, &options).unwrap_err(),
+            VersError::TooManyConstraints(10),
+        );
+        assert_eq!(
+            GenericVersionRange::<SemVer>::from_str_with_options(&input, &options).unwrap_err(),
+            VersError::TooManyConstraints(10),
+        );
+
+        // The limit is inclusive, and plain parsing stays unbounded
+        let options = ParseOptions { max_constraints: Some(100), ..Default::default() };
+        assert!(DynamicVersionRange::from_str_with_options(&input, &options).is_ok());
+        assert_eq!(parse(&input).unwrap().constraints().len(), 100);
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        // The redundant "1.2.3" is subsumed by "<2.0.0"
+        assert_eq!(canonicalize("vers:npm/ <2.0.0 | 1.2.3 ").unwrap(), "vers:npm/<2.0.0");
+        assert_eq!(canonicalize("vers:npm/ 3.0.0 | <2.0.0 ").unwrap(), "vers:npm/<2.0.0|3.0.0");
+        assert_eq!(canonicalize("vers:NPM/>=1.0.0|<2.0.0|>=3.0.0").unwrap(), "vers:npm/>=1.0.0|<2.0.0|>=3.0.0");
+        assert_eq!(canonicalize("vers:numeric/ 2.0 | 1.10 ").unwrap(), "vers:numeric/1.10|2.0");
+        assert_eq!(
+            canonicalize("vers:pypi/1.0"),
+            Err(VersError::UnsupportedVersioningSchemeWithHint {
+                scheme: "pypi".to_string(),
+                supported: schemes::supported_schemes(),
+            })
+        );
+        assert!(canonicalize("vers:npm/").is_err());
+    }
+
+    #[test]
+    fn test_scheme_aliases() {
+        let npm = parse("vers:npm/>=1.0.0|<2.0.0").unwrap();
+        let semver = parse("vers:semver/>=1.0.0|<2.0.0").unwrap();
+        let numeric = parse("vers:numeric/>=1.0.0|<2.0.0").unwrap();
+
+        assert_eq!(npm.canonical_scheme(), "semver");
+        assert_eq!(semver.canonical_scheme(), "semver");
+        assert_eq!(numeric.canonical_scheme(), "numeric");
+
+        assert_ne!(npm, semver);
+        assert!(npm.eq_ignoring_scheme_aliases(&semver));
+        assert!(semver.eq_ignoring_scheme_aliases(&npm));
+        assert!(!npm.eq_ignoring_scheme_aliases(&numeric));
+        assert!(!npm.eq_ignoring_scheme_aliases(&parse("vers:semver/>=1.0.0").unwrap()));
+
+        assert_eq!(schemes::canonical_scheme("NPM"), Some("semver"));
+        for scheme in schemes::supported_schemes() {
+            assert!(schemes::canonical_scheme(scheme).is_some());
+        }
+    }
+
+    #[test]
+    fn test_from_purl_with_range() {
+        let range = DynamicVersionRange::from_purl_with_range(
+            "pkg:npm/%40angular/core?vers=vers%3Anpm%2F%3E%3D1.0.0%7C%3C2.0.0&repository_url=example.com#src",
+        ).unwrap();
+        assert_eq!(range, "vers:npm/>=1.0.0|<2.0.0");
+        assert!(range.contains("1.5.0").unwrap());
+
+        // Purl types that are not versioning schemes are not cross-checked
+        let range = DynamicVersionRange::from_purl_with_range("pkg:cargo/serde?vers=vers%3Asemver%2F1.0.0").unwrap();
+        assert_eq!(range, "vers:semver/1.0.0");
+        let range = DynamicVersionRange::from_purl_with_range("pkg:npm/lodash?vers=vers%3Asemver%2F1.0.0").unwrap();
+        assert_eq!(range.versioning_scheme(), "semver");
+
+        assert_eq!(
+            DynamicVersionRange::from_purl_with_range("pkg:npm/lodash?vers=vers%3Anumeric%2F1.0"),
+            Err(VersError::IncompatibleVersioningSchemes("npm".to_string(), "numeric".to_string()))
+        );
+        for invalid in [
+            "npm/lodash?vers=vers%3Anpm%2F1.0.0",
+            "pkg:npm/lodash",
+            "pkg:npm/lodash?repository_url=example.com",
+            "pkg:lodash?vers=vers%3Anpm%2F1.0.0",
+        ] {
+            assert!(
+                matches!(DynamicVersionRange::from_purl_with_range(invalid), Err(VersError::InvalidPurl(_))),
+                "{} should be invalid",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_comparator_as_str() {
+        let symbols = [
+            (Comparator::Equal, "="),
+            (Comparator::NotEqual, "!="),
+            (Comparator::LessThan, "<"),
+            (Comparator::LessThanOrEqual, "<="),
+            (Comparator::GreaterThan, ">"),
+            (Comparator::GreaterThanOrEqual, ">="),
+            (Comparator::Any, "*"),
+        ];
+        for (comparator, symbol) in symbols {
+            assert_eq!(comparator.as_str(), symbol);
+            assert_eq!(comparator.to_string(), symbol);
+        }
+
+        const GREATER_THAN: &str = Comparator::GreaterThan.as_str();
+        assert_eq!(GREATER_THAN, ">");
+    }
+
+    #[test]
+    fn test_minimum_unaffected() {
+        let fixed = |s: &str| {
+            s.parse::<GenericVersionRange<SemVer>>().unwrap().minimum_unaffected().map(|v| v.to_string())
+        };
+        assert_eq!(fixed("vers:npm/<1.5.0"), Some("1.5.0".to_string()));
+        assert_eq!(fixed("vers:npm/<=1.5.0"), Some("1.5.1".to_string()));
+        assert_eq!(fixed("vers:npm/>=1.0.0|<1.5.0|!=1.2.0"), Some("1.5.0".to_string()));
+        assert_eq!(fixed("vers:npm/<1.0.0|>=2.0.0|<=2.5.0"), Some("2.5.1".to_string()));
+        assert_eq!(fixed("vers:npm/1.2.3"), Some("1.2.4".to_string()));
+        assert_eq!(fixed("vers:npm/>=1.0.0"), None);
+        assert_eq!(fixed("vers:npm/!=1.0.0"), None);
+        assert_eq!(fixed("vers:npm/*"), None);
+    }
+
+    #[test]
+    fn test_contains_boundaries() {
+        let ranges = [
+            "vers:npm/<1.0.0",
+            "vers:npm/<=1.0.0",
+            "vers:npm/>1.0.0",
+            "vers:npm/>=1.0.0",
+            "vers:npm/1.0.0|2.0.0",
+            "vers:npm/!=1.0.0|!=2.0.0",
+            "vers:npm/<1.0.0|>=2.0.0",
+            "vers:npm/<=1.0.0|>2.0.0|!=3.0.0",
+            "vers:npm/>=1.0.0|!=1.5.0|<=2.0.0|3.0.0|>4.0.0|<5.0.0",
+            "vers:npm/0.5.0|>1.0.0|<2.0.0|2.5.0|>=3.0.0",
+        ];
+        let probes = ["0.0.0", "0.5.0", "1.0.0", "1.0.1", "1.5.0", "2.0.0", "2.5.0", "3.0.0", "3.5.0", "4.0.0", "4.5.0", "5.0.0", "6.0.0"];
+
+        for input in ranges {
+            let range: GenericVersionRange<SemVer> = input.parse().unwrap();
+            let intervals = crate::range::interval::from_constraints(&range.constraints);
+            for probe in probes {
+                let version: SemVer = probe.parse().unwrap();
+                let expected = intervals.iter().any(|i| crate::range::interval::interval_contains(i, &version));
+                assert_eq!(range.contains(&version).unwrap(), expected, "{} in {}", probe, input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_constraint_parse_error() {
+        let error = parse("vers:npm/>=1.0.0|garbage").unwrap_err();
+        match error {
+            VersError::ConstraintParseError { index, source } => {
+                assert_eq!(index, 1);
+                assert!(matches!(*source, VersError::InvalidConstraint(_)));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        // Indices count the constraints as written, including ignored empty ones
+        let error = parse("vers:npm/>=1.0.0||<2.x").unwrap_err();
+        assert!(matches!(error, VersError::ConstraintParseError { index: 2, .. }));
+        assert!(std::error::Error::source(&error).is_some());
+
+        // Structural errors keep their own variants
+        assert_eq!(parse("vers:/1.0.0").unwrap_err(), VersError::MissingVersioningScheme);
+        assert_eq!(parse("foo:npm/1.0.0").unwrap_err(), VersError::InvalidScheme);
+        assert!(matches!(parse("vers:npm/1.0.0|1.0.0").unwrap_err(), VersError::DuplicateVersion(_)));
+    }
+
+    #[test]
+    fn test_error_kind() {
+        let cases = [
+            (VersError::InvalidScheme, ErrorKind::Input),
+            (VersError::MissingVersioningScheme, ErrorKind::Input),
+            (VersError::InvalidVersioningScheme("a@b".to_string()), ErrorKind::Input),
+            (VersError::EmptyConstraints, ErrorKind::Input),
+            (VersError::InvalidConstraint("x".to_string()), ErrorKind::Input),
+            (
+                VersError::ConstraintParseError { index: 0, source: Box::new(VersError::EmptyConstraints) },
+                ErrorKind::Input,
+            ),
+            (VersError::DuplicateVersion("1.0.0".to_string()), ErrorKind::Input),
+            (VersError::InvalidRange("x".to_string()), ErrorKind::Input),
+            (VersError::IncompatibleVersioningSchemes("npm".to_string(), "numeric".to_string()), ErrorKind::Input),
+            (VersError::UnsupportedVersioningScheme("pypi".to_string()), ErrorKind::UnsupportedScheme),
+            (
+                VersError::UnsupportedVersioningSchemeWithHint { scheme: "pypi".to_string(), supported: &["npm"] },
+                ErrorKind::UnsupportedScheme,
+            ),
+            (VersError::MembershipUnsupported("pypi".to_string()), ErrorKind::UnsupportedScheme),
+            (VersError::InvalidVersionFormat("numeric", "x".to_string(), "y".to_string()), ErrorKind::Input),
+            (VersError::TooManyConstraints(1), ErrorKind::Input),
+            (VersError::OrderingUnsupported("opaque".to_string()), ErrorKind::Input),
+            (VersError::InvalidPurl("x".to_string()), ErrorKind::Input),
+            (VersError::Serialization("x".to_string()), ErrorKind::Internal),
+        ];
+        for (error, kind) in cases {
+            assert_eq!(error.kind(), kind, "{:?}", error);
+            assert_eq!(error.is_recoverable(), kind == ErrorKind::Input);
+        }
+    }
+
+    #[test]
+    fn test_double_slash_prefix() {
+        let plain = parse("vers:npm/1.2.3").unwrap();
+        assert_eq!(parse("vers://npm/1.2.3").unwrap(), plain);
+        assert_eq!(parse("vers://npm/1.2.3").unwrap().to_string(), "vers:npm/1.2.3");
+        let range: GenericVersionRange<SemVer> = "vers://npm/>=1.0.0|<2.0.0".parse().unwrap();
+        assert_eq!(range, "vers:npm/>=1.0.0|<2.0.0");
+
+        assert_eq!(parse("vers:///1.2.3").unwrap_err(), VersError::MissingVersioningScheme);
+        assert_eq!(parse("vers://npm").unwrap_err(), VersError::MissingVersioningScheme);
+        assert!(parse("vers:////npm/1.2.3").is_err());
+    }
+
+    #[test]
+    fn test_contains_interval() {
+        use std::ops::Bound::*;
+
+        let v = |s: &str| s.parse::<SemVer>().unwrap();
+        let (v1, v1_5, v2, v3, v4) = (v("1.0.0"), v("1.5.0"), v("2.0.0"), v("3.0.0"), v("4.0.0"));
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<3.0.0|!=2.0.0".parse().unwrap();
+
+        // Fully contained
+        assert!(range.contains_interval(Included(&v1), Excluded(&v2)).unwrap());
+        assert!(range.contains_interval(Included(&v1_5), Included(&v1_5)).unwrap());
+        assert!(range.contains_interval(Excluded(&v2), Excluded(&v3)).unwrap());
+
+        // Partially overlapping
+        assert!(!range.contains_interval(Unbounded, Excluded(&v2)).unwrap());
+        assert!(!range.contains_interval(Excluded(&v2), Included(&v3)).unwrap());
+        assert!(!range.contains_interval(Included(&v3), Unbounded).unwrap());
+
+        // Punctured by the "!=" hole
+        assert!(!range.contains_interval(Included(&v1), Excluded(&v3)).unwrap());
+        assert!(!range.contains_interval(Included(&v1_5), Included(&v2)).unwrap());
+
+        let star: GenericVersionRange<SemVer> = "vers:npm/*".parse().unwrap();
+        assert!(star.contains_interval(Unbounded, Unbounded).unwrap());
+        assert!(!range.contains_interval(Unbounded, Unbounded).unwrap());
+
+        assert!(matches!(
+            range.contains_interval(Included(&v4), Excluded(&v1)),
+            Err(VersError::InvalidRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_to_string_explicit() {
+        let range = parse("vers:npm/=1.2.3|=2.0.0").unwrap();
+        assert_eq!(range.to_string(), "vers:npm/1.2.3|2.0.0");
+        assert_eq!(range.to_string_explicit(), "vers:npm/=1.2.3|=2.0.0");
+        assert_eq!(parse(&range.to_string_explicit()).unwrap(), range);
+
+        // Other comparators are written as in the canonical form
+        let range = parse("vers:npm/<1.0.0|1.5.0|>=2.0.0|!=2.5.0").unwrap();
+        assert_eq!(range.to_string(), "vers:npm/<1.0.0|1.5.0|>=2.0.0|!=2.5.0");
+        assert_eq!(range.to_string_explicit(), "vers:npm/<1.0.0|=1.5.0|>=2.0.0|!=2.5.0");
+
+        let range = parse("vers:numeric/*").unwrap();
+        assert_eq!(range.to_string_explicit(), range.to_string());
+    }
+
+    #[test]
+    fn test_filter_matching() {
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        let versions: Vec<SemVer> = ["0.9.0", "1.5.0", "2.0.0"].iter().map(|v| v.parse().unwrap()).collect();
+        let matching = range.filter_matching(versions).unwrap();
+        assert_eq!(matching.iter().map(|v| v.to_string()).collect::<Vec<_>>(), vec!["1.5.0"]);
+
+        // Input order is preserved
+        let dynamic = parse("vers:npm/>=1.0.0|<2.0.0").unwrap();
+        assert_eq!(
+            dynamic.filter_matching(["1.9.0", "0.9.0", "1.0.0", "1.5.0", "2.0.0"]).unwrap(),
+            vec!["1.9.0", "1.0.0", "1.5.0"]
+        );
+        assert!(dynamic.filter_matching(Vec::new()).unwrap().is_empty());
+        assert!(dynamic.filter_matching(["1.5.0", "not-a-version"]).is_err());
+    }
+
+    #[test]
+    fn test_constraint_multiple_comparators() {
+        for input in [">=>1.0.0", "<<1.0.0", "!=!=1.0.0", ">=>=1.0.0", "= =1.0.0", "<> <1.0.0"] {
+            assert_eq!(
+                VersionConstraint::<SemVer>::parse(input).unwrap_err(),
+                VersError::InvalidConstraint("Multiple comparators".to_string()),
+                "input: {}",
+                input
+            );
+        }
+        assert!(VersionConstraint::<SemVer>::parse("<>1.0.0").is_ok());
+        assert!(matches!(
+            parse("vers:npm/<<1.0.0").unwrap_err(),
+            VersError::ConstraintParseError { index: 0, source } if *source == VersError::InvalidConstraint("Multiple comparators".to_string())
+        ));
+    }
+
+    #[test]
+    fn test_prerelease_boundaries() {
+        assert!("1.0.0-rc1".parse::<SemVer>().unwrap().is_prerelease());
+        assert!(!"1.0.0+build.1".parse::<SemVer>().unwrap().is_prerelease());
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0-rc1|<2.0.0".parse().unwrap();
+        let boundaries: Vec<String> = range.prerelease_boundaries().iter().map(|v| v.to_string()).collect();
+        assert_eq!(boundaries, vec!["1.0.0-rc1"]);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0-alpha|!=1.5.0-beta".parse().unwrap();
+        let boundaries: Vec<String> = range.prerelease_boundaries().iter().map(|v| v.to_string()).collect();
+        assert_eq!(boundaries, vec!["1.5.0-beta", "2.0.0-alpha"]);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/*".parse().unwrap();
+        assert!(range.prerelease_boundaries().is_empty());
+    }
+
+    #[test]
+    fn test_from_str_with_aliases() {
+        let aliases = schemes::default_scheme_aliases();
+        for input in ["vers:nodejs/1.2.3", "vers:node/1.2.3", "vers:NPMJS/1.2.3"] {
+            let range = DynamicVersionRange::from_str_with_aliases(input, &aliases).unwrap();
+            assert!(matches!(range, DynamicVersionRange::SemVer(_)), "input: {}", input);
+            assert_eq!(range.versioning_scheme(), "npm");
+            assert_eq!(range, "vers:npm/1.2.3");
+        }
+
+        // Schemes without an alias are parsed as-is
+        let range = DynamicVersionRange::from_str_with_aliases("vers:semver/1.2.3", &aliases).unwrap();
+        assert_eq!(range.versioning_scheme(), "semver");
+        assert_eq!(
+            DynamicVersionRange::from_str_with_aliases("vers:golang/1.2.3", &aliases).unwrap_err(),
+            VersError::UnsupportedVersioningSchemeWithHint {
+                scheme: "golang".to_string(),
+                supported: schemes::supported_schemes(),
+            }
+        );
+
+        let custom = [("dotted".to_string(), "numeric".to_string())].into_iter().collect();
+        let range = DynamicVersionRange::from_str_with_aliases("vers:dotted/>=1.2|<2", &custom).unwrap();
+        assert_eq!(range, "vers:numeric/>=1.2|<2");
+    }
+
+    #[test]
+    fn test_describe() {
+        let describe = |s: &str| parse(s).unwrap().describe();
+        assert_eq!(
+            describe("vers:npm/>=1.0.0|<2.0.0|!=1.5.0"),
+            "versions 1.0.0 (inclusive) up to but not including 2.0.0, excluding 1.5.0 and pre-releases"
+        );
+        assert_eq!(
+            describe("vers:npm/<=1.0.0|1.5.0|>2.0.0"),
+            "all versions up to and including 1.0.0, or version 1.5.0, or versions above 2.0.0, \
+             excluding pre-releases"
+        );
+        assert_eq!(
+            describe("vers:npm/>=1.0.0-rc.1|<2.0.0"),
+            "versions 1.0.0-rc.1 (inclusive) up to but not including 2.0.0, \
+             excluding pre-releases other than those of 1.0.0"
+        );
+        assert_eq!(describe("vers:npm/1.0.0|2.0.0"), "version 1.0.0, or version 2.0.0");
+        assert_eq!(describe("vers:numeric/>=3"), "versions 3 (inclusive) and above");
+        assert_eq!(describe("vers:npm/*"), "all versions, excluding pre-releases");
+        assert_eq!(
+            describe("vers:npm/!=1.0.0|!=2.0.0"),
+            "all versions, excluding 1.0.0, 2.0.0 and pre-releases"
+        );
+
+        let mut range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        range.allow_prereleases(true);
+        assert_eq!(range.describe(), "versions 1.0.0 (inclusive) up to but not including 2.0.0");
+
+        let a: GenericVersionRange<SemVer> = "vers:npm/<2.0.0".parse().unwrap();
+        assert_eq!(a.difference(&a).unwrap().describe(), "no versions");
+    }
+
+    #[test]
+    fn test_versioning_scheme_charset() {
+        // Surrounding whitespace is stripped and uppercase is lowercased
+        assert_eq!(parse("vers: npm /1.2.3").unwrap().versioning_scheme(), "npm");
+        assert_eq!(parse("vers:NPM/1.2.3").unwrap().versioning_scheme(), "npm");
+
+        for scheme in ["np@m", "n pm", "npm_js", "np%6D"] {
+            let input = format!("vers:{}/1.2.3", scheme);
+            assert_eq!(
+                parse(&input).unwrap_err(),
+                VersError::InvalidVersioningScheme(scheme.to_lowercase()),
+                "input: {}",
+                input
+            );
+            let result: Result<GenericVersionRange<SemVer>, _> = input.parse();
+            assert!(matches!(result, Err(VersError::InvalidVersioningScheme(_))));
+        }
+
+        // Valid but unsupported schemes are still reported as unsupported
+        assert!(matches!(parse("vers:foo.bar-baz+1/1.2.3"), Err(VersError::UnsupportedVersioningSchemeWithHint { .. })));
+    }
+
+    #[test]
+    fn test_normalization_preserves_membership() {
+        // The membership of each version in the written range, before normalization, is
+        // compared with the normalized range, which also runs the debug-build check
+        let check = |s: &str, members: &[(&str, bool)]| {
+            let range: GenericVersionRange<SemVer> = s.parse().unwrap();
+            for (version, expected) in members {
+                assert_eq!(range.contains(&version.parse().unwrap()).unwrap(), *expected, "{} in {}", version, s);
+            }
+        };
+
+        check(
+            "vers:npm/>=0.5.0|<1.0.0|<1.5.0|1.2.0|>=2.0.0|>=2.5.0|!=2.7.0|<3.0.0|4.0.0|>5.0.0|>=6.0.0",
+            &[
+                ("0.4.0", false),
+                ("0.5.0", true),
+                ("1.0.0", true),
+                ("1.2.0", true),
+                ("1.5.0", false),
+                ("2.0.0", true),
+                ("2.5.0", true),
+                ("2.7.0", false),
+                ("3.0.0", false),
+                ("4.0.0", true),
+                ("5.0.0", false),
+                ("5.5.0", true),
+                ("6.0.0", true),
+            ],
+        );
+        check(
+            "vers:npm/<1.0.0|<=1.5.0|1.5.0-rc.1|2.0.0|>2.1.0",
+            &[
+                ("1.0.0", true),
+                ("1.5.0-rc.1", true),
+                ("1.5.0-beta", true),
+                ("1.5.0", true),
+                ("1.6.0", false),
+                ("2.0.0", true),
+                ("2.0.0-rc.1", false),
+                ("2.1.0", false),
+                ("3.0.0", true),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_as_ref_constraints() {
+        fn count_exclusions<V: constraint::VT>(constraints: impl AsRef<[VersionConstraint<V>]>) -> usize {
+            constraints.as_ref().iter().filter(|c| c.comparator == Comparator::NotEqual).count()
+        }
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|!=1.5.0|<2.0.0".parse().unwrap();
+        assert_eq!(count_exclusions(&range), 1);
+        assert_eq!(range.as_ref(), range.constraints.as_slice());
+    }
+
+    #[test]
+    fn test_to_npm_range() {
+        let npm = |s: &str| s.parse::<GenericVersionRange<SemVer>>().unwrap().to_npm_range();
+
+        // Caret
+        assert_eq!(npm("vers:npm/>=1.2.3|<2.0.0"), Some("^1.2.3".to_string()));
+        assert_eq!(npm("vers:npm/>=0.2.3|<0.3.0"), Some("^0.2.3".to_string()));
+        assert_eq!(npm("vers:npm/>=0.0.3|<0.0.4"), Some("^0.0.3".to_string()));
+        assert_eq!(npm("vers:npm/>=1.0.0-beta.1|<2.0.0"), Some("^1.0.0-beta.1".to_string()));
+
+        // Tilde
+        assert_eq!(npm("vers:npm/>=1.2.3|<1.3.0"), Some("~1.2.3".to_string()));
+        assert_eq!(npm("vers:npm/>=0.0.3|<0.1.0"), Some("~0.0.3".to_string()));
+
+        // No compact form
+        assert_eq!(npm("vers:npm/>=1.2.3|<1.5.0"), None);
+        assert_eq!(npm("vers:npm/>1.2.3|<2.0.0"), None);
+        assert_eq!(npm("vers:npm/>=1.2.3|<=2.0.0"), None);
+        assert_eq!(npm("vers:npm/>=1.2.3|!=1.5.0|<2.0.0"), None);
+        assert_eq!(npm("vers:npm/>=1.2.3"), None);
+        assert_eq!(npm("vers:npm/*"), None);
+    }
+
+    #[test]
+    fn test_opaque_ordering_unsupported() {
+        use crate::schemes::opaque::OpaqueVersion;
+
+        let result: Result<GenericVersionRange<OpaqueVersion>, _> = "vers:opaque/>1.0".parse();
+        assert_eq!(result.unwrap_err(), VersError::OrderingUnsupported("opaque".to_string()));
+        let result: Result<GenericVersionRange<OpaqueVersion>, _> = "vers:opaque/abc|<=def".parse();
+        assert_eq!(result.unwrap_err(), VersError::OrderingUnsupported("opaque".to_string()));
+
+        let range: GenericVersionRange<OpaqueVersion> = "vers:opaque/abc|1.0".parse().unwrap();
+        assert!(range.contains(&"abc".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"1.1".parse().unwrap()).unwrap());
+
+        let range: GenericVersionRange<OpaqueVersion> = "vers:opaque/!=abc".parse().unwrap();
+        assert!(!range.contains(&"abc".parse().unwrap()).unwrap());
+        assert!(range.contains(&"xyz".parse().unwrap()).unwrap());
+
+        // Ranges constructed without validation are checked when validated or prepared
+        let range = GenericVersionRange::new(
+            "opaque".to_string(),
+            vec![VersionConstraint::new(Comparator::LessThan, "2.0".parse::<OpaqueVersion>().unwrap())],
+        );
+        assert_eq!(
+            range.clone().normalize_and_validate().unwrap_err(),
+            VersError::OrderingUnsupported("opaque".to_string())
+        );
+        assert_eq!(range.into_prepared().unwrap_err(), VersError::OrderingUnsupported("opaque".to_string()));
+    }
+
+    #[test]
+    fn test_contains_matrix() {
+        let ranges = [
+            parse("vers:npm/>=1.0.0|<2.0.0").unwrap(),
+            parse("vers:numeric/>=1.5").unwrap(),
+            parse("vers:semver/!=1.5.0").unwrap(),
+            parse("vers:numeric/*").unwrap(),
+        ];
+        let versions = ["0.9.0", "1.5.0", "2.0.0", "1.5", "not-a-version"];
+
+        let matrix = contains_matrix(&ranges, &versions);
+        assert_eq!(matrix.len(), ranges.len());
+        for (range, row) in ranges.iter().zip(&matrix) {
+            assert_eq!(row.len(), versions.len());
+            for (version, cell) in versions.iter().zip(row) {
+                assert_eq!(*cell, range.contains(version), "{} in {}", version, range);
+            }
+        }
+        assert!(contains_matrix(&[], &versions).is_empty());
+    }
+
+    #[test]
+    fn test_comparator_predicates() {
+        // (comparator, lower bound, upper bound, inclusive, exact)
+        let cases = [
+            (Comparator::Equal, false, false, true, true),
+            (Comparator::NotEqual, false, false, false, true),
+            (Comparator::LessThan, false, true, false, false),
+            (Comparator::LessThanOrEqual, false, true, true, false),
+            (Comparator::GreaterThan, true, false, false, false),
+            (Comparator::GreaterThanOrEqual, true, false, true, false),
+            (Comparator::Any, false, false, false, false),
+        ];
+        for (comparator, lower, upper, inclusive, exact) in cases {
+            assert_eq!(comparator.is_lower_bound(), lower, "{}", comparator);
+            assert_eq!(comparator.is_upper_bound(), upper, "{}", comparator);
+            assert_eq!(comparator.is_inclusive(), inclusive, "{}", comparator);
+            assert_eq!(comparator.is_exact(), exact, "{}", comparator);
+        }
+    }
+
+    #[test]
+    fn test_contains_multi_interval_gaps() {
+        let range: GenericVersionRange<SemVer> =
+            "vers:npm/>1.0.0|!=1.2.0|<1.5.0|>2.0.0|!=2.2.0|<2.5.0".parse().unwrap();
+        let cases = [
+            ("0.5.0", false),
+            ("1.0.0", false),
+            ("1.1.0", true),
+            ("1.2.0", false),
+            ("1.4.0", true),
+            ("1.5.0", false),
+            // In the gap between the intervals
+            ("1.7.0", false),
+            ("2.0.0", false),
+            ("2.1.0", true),
+            ("2.2.0", false),
+            ("2.4.0", true),
+            // Above the last interval
+            ("2.5.0", false),
+            ("3.0.0", false),
+        ];
+        for (version, expected) in cases {
+            assert_eq!(range.contains(&version.parse().unwrap()).unwrap(), expected, "{}", version);
+        }
+
+        // The same holds when the last interval is open-ended
+        let range: GenericVersionRange<SemVer> = "vers:npm/<1.0.0|>2.0.0|!=3.0.0".parse().unwrap();
+        for (version, expected) in [("0.5.0", true), ("1.5.0", false), ("2.0.0", false), ("3.0.0", false), ("4.0.0", true)] {
+            assert_eq!(range.contains(&version.parse().unwrap()).unwrap(), expected, "{}", version);
+        }
+    }
+
+    #[test]
+    fn test_exclude_prereleases() {
+        let input = "vers:npm/>=1.0.0-rc1|<2.0.0-alpha";
+        let stable = ParseOptions { exclude_prereleases: true, ..Default::default() };
+
+        let range = DynamicVersionRange::from_str_with_options(input, &ParseOptions::default()).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0-rc1|<2.0.0-alpha");
+        assert!(range.contains("1.0.0-rc2").unwrap());
+
+        let range = DynamicVersionRange::from_str_with_options(input, &stable).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<2.0.0");
+        assert!(!range.contains("1.0.0-rc2").unwrap());
+        assert!(range.contains("1.0.0").unwrap());
+        assert!(!range.contains("2.0.0").unwrap());
+
+        let range = DynamicVersionRange::from_str_with_options("vers:npm/>1.0.0-rc1|<=2.0.0-alpha", &stable).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<2.0.0");
+
+        for input in ["vers:npm/1.0.0-rc1", "vers:npm/>=1.0.0|!=1.5.0-beta"] {
+            let error = DynamicVersionRange::from_str_with_options(input, &stable).unwrap_err();
+            assert!(matches!(
+                error,
+                VersError::ConstraintParseError { source, .. } if matches!(*source, VersError::InvalidConstraint(_))
+            ), "input: {}", input);
+        }
+
+        // Schemes without pre-releases are unaffected
+        let range = DynamicVersionRange::from_str_with_options("vers:numeric/>1.0|<=2.0", &stable).unwrap();
+        assert_eq!(range.to_string(), "vers:numeric/>1.0|<=2.0");
+    }
+
+    #[test]
+    fn test_materialize() {
+        let v = |s: &str| s.parse::<SemVer>().unwrap();
+        let known: Vec<SemVer> = ["2.0.0", "0.9.0", "1.5.0", "1.0.0", "1.9.9", "1.5.0"].iter().map(|s| v(s)).collect();
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        let materialized = range.materialize(&known).unwrap();
+        assert_eq!(materialized.to_string(), "vers:npm/1.0.0|1.5.0|1.9.9");
+        for version in &known {
+            assert_eq!(materialized.contains(version).unwrap(), range.contains(version).unwrap());
+        }
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=0.1.0".parse().unwrap();
+        assert_eq!(range.materialize(&known).unwrap().to_string(), "vers:npm/*");
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=3.0.0".parse().unwrap();
+        let materialized = range.materialize(&known).unwrap();
+        assert!(materialized.is_empty());
+        assert!(range.materialize(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_interval_count() {
+        let range = |s: &str| s.parse::<GenericVersionRange<SemVer>>().unwrap();
+
+        assert_eq!(range("vers:npm/>=1.0.0|<2.0.0").interval_count(), 1);
+        assert!(range("vers:npm/>=1.0.0|<2.0.0").is_single_interval());
+        assert_eq!(range("vers:npm/>=1.0.0|<2.0.0|>=3.0.0|<4.0.0").interval_count(), 2);
+        assert!(!range("vers:npm/>=1.0.0|<2.0.0|>=3.0.0|<4.0.0").is_single_interval());
+        assert!(range("vers:npm/*").is_single_interval());
+        assert!(range("vers:npm/!=1.0.0").is_single_interval());
+        assert_eq!(range("vers:npm/1.0.0|2.0.0|>=3.0.0").interval_count(), 3);
+
+        let a = range("vers:npm/<2.0.0");
+        assert_eq!(a.difference(&a).unwrap().interval_count(), 0);
+    }
+
+    #[test]
+    fn test_all_supported_schemes_dispatch() {
+        for scheme in schemes::supported_schemes() {
+            let version = schemes::example_version(scheme).unwrap();
+            assert!(schemes::validate(scheme, &version).is_ok(), "scheme: {}", scheme);
+            assert!(schemes::canonical_scheme(scheme).is_some(), "scheme: {}", scheme);
+
+            for input in [
+                format!("vers:{}/{}", scheme, version),
+                format!("vers:{}/>={}", scheme, version),
+                format!("vers:{}/!={}", scheme, version),
+                format!("vers:{}/*", scheme),
+            ] {
+                let range = parse(&input)
+                    .unwrap_or_else(|e| panic!("{} failed to parse: {}", input, e));
+                assert_eq!(range.versioning_scheme(), *scheme);
+                assert_eq!(range.to_string(), input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_union_and_intersect_with_any() {
+        let any: GenericVersionRange<SemVer> = "vers:npm/*".parse().unwrap();
+        let at_least: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0".parse().unwrap();
+
+        assert_eq!(any.union(&at_least).unwrap().to_string(), "vers:npm/*");
+        assert_eq!(at_least.union(&any).unwrap().to_string(), "vers:npm/*");
+        assert_eq!(any.intersect(&at_least).unwrap().to_string(), "vers:npm/>=1.0.0");
+        assert_eq!(at_least.intersect(&any).unwrap().to_string(), "vers:npm/>=1.0.0");
+        assert_eq!(any.intersect(&any).unwrap().to_string(), "vers:npm/*");
+        assert_eq!(any.union(&any).unwrap().to_string(), "vers:npm/*");
+    }
+
+    #[test]
+    fn test_union_and_intersect() {
+        let a: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|!=1.5.0|<2.0.0".parse().unwrap();
+        let b: GenericVersionRange<SemVer> = "vers:npm/>=3.0.0".parse().unwrap();
+        let c: GenericVersionRange<SemVer> = "vers:npm/<=1.5.0".parse().unwrap();
+
+        assert_eq!(a.union(&b).unwrap().to_string(), "vers:npm/>=1.0.0|!=1.5.0|<2.0.0|>=3.0.0");
+        assert_eq!(a.union(&c).unwrap().to_string(), "vers:npm/<2.0.0");
+        assert!(a.intersect(&b).unwrap().is_empty());
+        assert_eq!(a.intersect(&c).unwrap().to_string(), "vers:npm/>=1.0.0|<1.5.0");
+
+        let other: GenericVersionRange<SemVer> = "vers:semver/*".parse().unwrap();
+        assert!(matches!(
+            a.union(&other).unwrap_err(),
+            VersError::IncompatibleVersioningSchemes(..)
+        ));
+        assert!(matches!(
+            a.intersect(&other).unwrap_err(),
+            VersError::IncompatibleVersioningSchemes(..)
+        ));
+    }
+
+    #[test]
+    fn test_single_point_bounds_collapse() {
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.2.3|<=1.2.3".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/1.2.3");
+        assert_eq!(range.exact_match_count(), 1);
+        assert!(range.contains(&"1.2.3".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"1.2.4".parse().unwrap()).unwrap());
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/<1.0.0|>=1.2.3|<=1.2.3|>=2.0.0".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/<1.0.0|1.2.3|>=2.0.0");
+
+        // Bounds on the same version excluding it contain no version at all, which cannot be
+        // written as a vers string, so they are rejected when parsing and reported by is_empty
+        for constraints in [">1.2.3|<1.2.3", ">=1.2.3|<1.2.3", ">1.2.3|<=1.2.3"] {
+            let result = format!("vers:npm/{}", constraints).parse::<GenericVersionRange<SemVer>>();
+            assert_eq!(
+                result.unwrap_err().to_string(),
+                format!("Invalid version range: Empty range: vers:npm/{}", constraints)
+            );
+            let (lower, upper) = constraints.split_once('|').unwrap();
+            let range = GenericVersionRange::new(
+                "npm".to_string(),
+                vec![VersionConstraint::<SemVer>::parse(lower).unwrap(), VersionConstraint::parse(upper).unwrap()],
+            );
+            assert!(range.is_empty(), "{}", constraints);
+        }
+    }
+
+    #[test]
+    fn test_contains_mixed_inclusive_exclusive_boundaries() {
+        // For every combination of lower and upper comparator: the version below the lower
+        // bound, the lower bound, a version inside, the upper bound and the version above it
+        let cases = [
+            (">=1.0.0|<=2.0.0", [false, true, true, true, false]),
+            (">=1.0.0|<2.0.0", [false, true, true, false, false]),
+            (">1.0.0|<=2.0.0", [false, false, true, true, false]),
+            (">1.0.0|<2.0.0", [false, false, true, false, false]),
+        ];
+        let versions = ["0.9.9", "1.0.0", "1.5.0", "2.0.0", "2.0.1"];
+
+        for (constraints, expected) in cases {
+            let range: GenericVersionRange<SemVer> = format!("vers:semver/{}", constraints).parse().unwrap();
+            for (version, expected) in versions.iter().zip(expected) {
+                assert_eq!(
+                    range.contains(&version.parse().unwrap()).unwrap(),
+                    expected,
+                    "{} in {}",
+                    version,
+                    constraints,
+                );
+            }
+
+            // The same boundaries as the second interval of a range
+            let range: GenericVersionRange<SemVer> = format!("vers:semver/<0.5.0|{}", constraints).parse().unwrap();
+            for (version, expected) in versions.iter().zip(expected) {
+                assert_eq!(
+                    range.contains(&version.parse().unwrap()).unwrap(),
+                    expected,
+                    "{} in <0.5.0|{}",
+                    version,
+                    constraints,
+                );
+            }
+        }
+    }
+
+    /// A Docker-like tag, where tags starting with a digit are ordered and named tags such
+    /// as `latest` are not.
+    #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+    struct Tag(String);
+
+    impl std::fmt::Display for Tag {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl std::str::FromStr for Tag {
+        type Err = VersError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Tag(s.to_string()))
+        }
+    }
+
+    impl crate::constraint::VT for Tag {
+        fn is_comparable(&self) -> bool {
+            self.0.starts_with(|c: char| c.is_ascii_digit())
+        }
+    }
+
+    #[test]
+    fn test_incomparable_versions() {
+        let tag = |s: &str| -> Tag { s.parse().unwrap() };
+
+        let range: GenericVersionRange<Tag> = "vers:docker/=latest|!=broken".parse().unwrap();
+        assert!(range.contains(&tag("latest")).unwrap());
+        assert!(!range.contains(&tag("broken")).unwrap());
+        assert!(!range.contains(&tag("nightly")).unwrap());
+        assert!(!range.contains(&tag("1.0")).unwrap());
+
+        let range: GenericVersionRange<Tag> = "vers:docker/!=broken".parse().unwrap();
+        assert!(range.contains(&tag("latest")).unwrap());
+        assert!(!range.contains(&tag("broken")).unwrap());
+
+        // Incomparable versions take no part in intervals, even if sorted into one
+        let range: GenericVersionRange<Tag> = "vers:docker/>=1.0|latest".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:docker/>=1.0|latest");
+        assert!(range.contains(&tag("latest")).unwrap());
+        assert!(range.contains(&tag("2.0")).unwrap());
+        assert!(!range.contains(&tag("0.9")).unwrap());
+        assert!(!range.contains(&tag("broken")).unwrap());
+
+        let range: GenericVersionRange<Tag> = "vers:docker/>=1.0|!=1.5|<2.0|!=broken".parse().unwrap();
+        assert!(range.contains(&tag("1.2")).unwrap());
+        assert!(!range.contains(&tag("1.5")).unwrap());
+        assert!(!range.contains(&tag("latest")).unwrap());
+
+        assert!(matches!(
+            "vers:docker/>=latest".parse::<GenericVersionRange<Tag>>().unwrap_err(),
+            VersError::InvalidConstraint(_)
+        ));
+    }
+
+    #[test]
+    fn test_vuln_summary() {
+        use std::ops::Bound;
+
+        let v = |s: &str| -> SemVer { s.parse().unwrap() };
+
+        let range: GenericVersionRange<SemVer> =
+            "vers:npm/>=1.0.0|!=1.2.0|<1.5.0|>2.0.0|<=2.3.0".parse().unwrap();
+        let summary = range.vuln_summary();
+        assert_eq!(
+            summary.affected_intervals,
+            vec![
+                (Bound::Included(v("1.0.0")), Bound::Excluded(v("1.5.0"))),
+                (Bound::Excluded(v("2.0.0")), Bound::Included(v("2.3.0"))),
+            ]
+        );
+        assert_eq!(summary.fixed_in, vec![v("1.5.0"), v("2.3.1")]);
+        assert_eq!(summary.excluded, vec![v("1.2.0")]);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/<1.0.0|>=2.0.0".parse().unwrap();
+        let summary = range.vuln_summary();
+        assert_eq!(summary.affected_intervals.len(), 2);
+        assert_eq!(summary.fixed_in, vec![v("1.0.0")]);
+        assert!(summary.excluded.is_empty());
+    }
+
+    #[test]
+    fn test_parse_without_percent_decoding() {
+        let raw = ParseOptions { decode_percent: false, ..Default::default() };
+
+        let range = GenericVersionRange::<String>::from_str_with_options("vers:generic/1.0.0%2Bbuild", &raw).unwrap();
+        assert_eq!(range.as_ref()[0].version.to_string(), "1.0.0%2Bbuild");
+        let range = GenericVersionRange::<String>::from_str_with_options(
+            "vers:generic/1.0.0%2Bbuild",
+            &ParseOptions::default(),
+        ).unwrap();
+        assert_eq!(range.as_ref()[0].version.to_string(), "1.0.0+build");
+
+        // Without decoding, SemVer rejects the encoded build metadata
+        let result = GenericVersionRange::<SemVer>::from_str_with_options("vers:npm/1.0.0%2Bbuild", &raw);
+        assert!(matches!(
+            result.unwrap_err(),
+            VersError::ConstraintParseError { index: 0, source } if matches!(*source, VersError::InvalidConstraint(_))
+        ));
+        let range = GenericVersionRange::<SemVer>::from_str_with_options(
+            "vers:npm/1.0.0%2Bbuild",
+            &ParseOptions::default(),
+        ).unwrap();
+        assert_eq!(range.as_ref()[0].version, "1.0.0+build".parse().unwrap());
+
+        // Malformed escapes are kept literally as well
+        let range = GenericVersionRange::<String>::from_str_with_options("vers:generic/100%", &raw).unwrap();
+        assert_eq!(range.as_ref()[0].version, "100%");
+
+        // Display encodes the literal "%", so the range only round-trips with decoding on
+        let range = GenericVersionRange::<String>::from_str_with_options("vers:generic/1.0.0%2Bbuild", &raw).unwrap();
+        assert_eq!(range.to_string(), "vers:generic/1.0.0%252Bbuild");
+        assert_eq!(range.to_string().parse::<GenericVersionRange<String>>().unwrap(), range);
+        assert_ne!(GenericVersionRange::<String>::from_str_with_options(&range.to_string(), &raw).unwrap(), range);
+    }
+
+    #[test]
+    fn test_try_from_parts() {
+        let c = |s: &str| -> VersionConstraint<SemVer> { VersionConstraint::parse(s).unwrap() };
+
+        let range = GenericVersionRange::try_from_parts(
+            "npm".to_string(),
+            vec![c("<3.0.0"), c(">=1.0.0"), c(">=1.5.0"), c("!=2.0.0")],
+        ).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0|!=2.0.0|<3.0.0");
+
+        assert!(matches!(
+            GenericVersionRange::<SemVer>::try_from_parts("npm".to_string(), vec![]).unwrap_err(),
+            VersError::EmptyConstraints
+        ));
+        assert!(matches!(
+            GenericVersionRange::try_from_parts("npm".to_string(), vec![c(">=1.0.0"), c(">1.0.0")]).unwrap_err(),
+            VersError::DuplicateVersion(_)
+        ));
+        assert!(matches!(
+            GenericVersionRange::try_from_parts("npm".to_string(), vec![c(">=1.0.0"), c("<1.0.0")]).unwrap_err(),
+            VersError::InvalidRange(_)
+        ));
+        assert!(matches!(
+            GenericVersionRange::try_from_parts("npm".to_string(), vec![c("*"), c(">=1.0.0")]).unwrap_err(),
+            VersError::InvalidRange(_)
+        ));
+    }
+
+    #[test]
+    fn test_build_metadata_round_trip() {
+        let range: GenericVersionRange<SemVer> = "vers:npm/1.0.0+build.1".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/1.0.0+build.1");
+        assert_eq!(format!("{:#}", range), "vers:npm/1.0.0%2Bbuild.1");
+        assert_eq!(range.to_string().parse::<GenericVersionRange<SemVer>>().unwrap(), range);
+        assert_eq!(format!("{:#}", range).parse::<GenericVersionRange<SemVer>>().unwrap(), range);
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0-rc.1+build.5|<2.0.0%2Bexp.sha.5114f85".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0-rc.1+build.5|<2.0.0+exp.sha.5114f85");
+
+        // Padding partial versions keeps the build metadata
+        let range: GenericVersionRange<SemVer> = "vers:npm/1.0+build.1".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/1.0.0+build.1");
+
+        let range = parse("vers:semver/1.0.0+build.1").unwrap();
+        assert_eq!(range.to_string(), "vers:semver/1.0.0+build.1");
+    }
+
+    #[test]
+    fn test_internal_gaps() {
+        use std::ops::Bound;
+        let v = |s: &str| -> SemVer { s.parse().unwrap() };
+        let gaps = |s: &str| s.parse::<GenericVersionRange<SemVer>>().unwrap().internal_gaps();
+
+        assert!(gaps("vers:npm/>=1.0.0|<2.0.0").is_empty());
+        assert!(gaps("vers:npm/<2.0.0").is_empty());
+        assert!(gaps("vers:npm/*").is_empty());
+        assert!(gaps("vers:npm/1.0.0").is_empty());
+
+        assert_eq!(
+            gaps("vers:npm/>=1.0.0|<1.5.0|>=2.0.0|<3.0.0"),
+            vec![(Bound::Included(v("1.5.0")), Bound::Excluded(v("2.0.0")))]
+        );
+        assert_eq!(
+            gaps("vers:npm/<=1.0.0|1.2.0|>2.0.0"),
+            vec![
+                (Bound::Excluded(v("1.0.0")), Bound::Excluded(v("1.2.0"))),
+                (Bound::Excluded(v("1.2.0")), Bound::Included(v("2.0.0"))),
+            ]
+        );
+        assert_eq!(
+            gaps("vers:npm/>=1.0.0|!=1.5.0|<2.0.0"),
+            vec![(Bound::Included(v("1.5.0")), Bound::Included(v("1.5.0")))]
+        );
+    }
+
+    #[test]
+    fn test_only_exclusions_round_trip() {
+        let range: GenericVersionRange<SemVer> = "vers:npm/!=2.0.0|!=1.0.0".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/!=1.0.0|!=2.0.0");
+        assert_eq!(range.to_string().parse::<GenericVersionRange<SemVer>>().unwrap(), range);
+        assert!(range.contains(&"1.5.0".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"1.0.0".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"2.0.0".parse().unwrap()).unwrap());
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/!=1.2.3".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/!=1.2.3");
+
+        assert!(matches!(
+            "vers:npm/!=1.0.0|!=1.0.0".parse::<GenericVersionRange<SemVer>>().unwrap_err(),
+            VersError::DuplicateVersion(v) if v == "1.0.0"
+        ));
+    }
+
+    #[test]
+    fn test_merge_adjacent_intervals() {
+        let normalized = |s: &str| format!("vers:npm/{}", s).parse::<GenericVersionRange<SemVer>>().unwrap().to_string();
+
+        // Touching intervals merge
+        assert_eq!(normalized(">=1.0.0|<2.0.0|>=2.0.0|<3.0.0"), "vers:npm/>=1.0.0|<3.0.0");
+        assert_eq!(normalized(">=1.0.0|<=2.0.0|>2.0.0|<3.0.0"), "vers:npm/>=1.0.0|<3.0.0");
+        assert_eq!(normalized(">=1.0.0|<=2.0.0|>=2.0.0|<3.0.0"), "vers:npm/>=1.0.0|<3.0.0");
+        assert_eq!(normalized("<2.0.0|>=2.0.0"), "vers:npm/*");
+
+        // Intervals separated by a single version merge into one excluding it
+        assert_eq!(normalized(">=1.0.0|<2.0.0|>2.0.0|<3.0.0"), "vers:npm/>=1.0.0|!=2.0.0|<3.0.0");
+        assert_eq!(normalized("<2.0.0|>2.0.0"), "vers:npm/!=2.0.0");
+
+        // Intervals separated by a gap stay apart
+        assert_eq!(normalized(">=1.0.0|<2.0.0|>=2.5.0|<3.0.0"), "vers:npm/>=1.0.0|<2.0.0|>=2.5.0|<3.0.0");
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0|>=2.0.0|<3.0.0".parse().unwrap();
+        assert!(range.contains(&"2.0.0".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"3.0.0".parse().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_dynamic_range_conversions() {
+        let typed: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        let dynamic = DynamicVersionRange::from(typed.clone());
+        assert_eq!(dynamic, parse("vers:npm/>=1.0.0|<2.0.0").unwrap());
+        assert!(dynamic.contains("1.5.0").unwrap());
+
+        let back = GenericVersionRange::<SemVer>::try_from(dynamic).unwrap();
+        assert_eq!(back, typed);
+
+        let numeric = parse("vers:numeric/>=1.0").unwrap();
+        assert!(matches!(
+            GenericVersionRange::<SemVer>::try_from(numeric).unwrap_err(),
+            VersError::IncompatibleVersioningSchemes(scheme, _) if scheme == "numeric"
+        ));
+    }
+
+    #[test]
+    fn test_check_canonical() {
+        use crate::range::CanonicalViolation::*;
+        let range = |constraints: &[&str]| GenericVersionRange::<SemVer>::new(
+            "npm".to_string(),
+            constraints.iter().map(|c| VersionConstraint::parse(c).unwrap()).collect(),
+        );
+
+        let parsed: GenericVersionRange<SemVer> = "vers:npm/<1.0.0|1.5.0|>=2.0.0|!=2.5.0|<3.0.0".parse().unwrap();
+        assert_eq!(parsed.check_canonical(), Ok(()));
+        assert_eq!(range(&["*"]).check_canonical(), Ok(()));
+
+        assert_eq!(range(&[]).check_canonical(), Err(vec![EmptyConstraints]));
+        assert_eq!(range(&["*", ">=1.0.0"]).check_canonical(), Err(vec![StarNotAlone { index: 0 }]));
+        assert_eq!(
+            range(&["<2.0.0", ">=1.0.0"]).check_canonical(),
+            Err(vec![Unsorted { index: 1 }])
+        );
+        assert_eq!(
+            range(&[">=1.0.0", "<1.0.0"]).check_canonical(),
+            Err(vec![DuplicateVersion { index: 1 }])
+        );
+        assert_eq!(
+            range(&["1.0.0", "!=1.5.0", "<2.0.0"]).check_canonical(),
+            Err(vec![DisallowedAfterEqual { index: 2 }])
+        );
+        assert_eq!(
+            range(&[">=1.0.0", ">=1.5.0", "<2.0.0", "<3.0.0"]).check_canonical(),
+            Err(vec![RedundantBound { index: 1 }, RedundantBound { index: 2 }])
+        );
+        assert_eq!(
+            range(&[">=2.0.0", "<1.0.0", "<3.0.0"]).check_canonical(),
+            Err(vec![Unsorted { index: 1 }, RedundantBound { index: 1 }])
+        );
+    }
+
+    #[test]
+    fn test_from_scheme_and_constraints() {
+        for (scheme, constraints) in [("npm", ">=1.0.0|<2.0.0"), ("semver", "1.2.3|!=1.2.4"), ("numeric", "<2|>=3"), ("npm", "*")] {
+            assert_eq!(
+                DynamicVersionRange::from_scheme_and_constraints(scheme, constraints).unwrap(),
+                parse(&format!("vers:{}/{}", scheme, constraints)).unwrap()
+            );
+        }
+
+        assert!(matches!(
+            DynamicVersionRange::from_scheme_and_constraints("pypi", ">=1.0").unwrap_err(),
+            VersError::UnsupportedVersioningSchemeWithHint { .. }
+        ));
+        assert!(matches!(
+            DynamicVersionRange::from_scheme_and_constraints("", ">=1.0.0").unwrap_err(),
+            VersError::MissingVersioningScheme
+        ));
+        assert!(matches!(
+            DynamicVersionRange::from_scheme_and_constraints("npm", "").unwrap_err(),
+            VersError::EmptyConstraints
+        ));
+    }
+
+    #[test]
+    fn test_minimal_form() {
+        let cases = [
+            ("vers:npm/>=1.0.0|<2.0.0|!=3.0.0", "vers:npm/>=1.0.0|<2.0.0"),
+            ("vers:npm/1.0.0|!=2.0.0", "vers:npm/1.0.0"),
+            ("vers:npm/!=0.5.0|>=1.0.0|!=1.5.0|<2.0.0", "vers:npm/>=1.0.0|!=1.5.0|<2.0.0"),
+            ("vers:npm/<1.0.0|!=1.5.0|>=2.0.0", "vers:npm/<1.0.0|>=2.0.0"),
+        ];
+        for (input, minimal) in cases {
+            let range: GenericVersionRange<SemVer> = input.parse().unwrap();
+            let shrunk = range.minimal_form();
+            assert_eq!(shrunk.to_string(), minimal);
+            assert!(shrunk.constraints.len() < range.constraints.len(), "{}", input);
+            assert!(shrunk.is_equivalent(&range).unwrap(), "{}", input);
+        }
+
+        // Already minimal ranges are kept as-is
+        for input in ["vers:npm/*", "vers:npm/!=1.0.0|!=2.0.0", "vers:npm/<1.0.0|1.5.0|>2.0.0"] {
+            let range: GenericVersionRange<SemVer> = input.parse().unwrap();
+            assert_eq!(range.minimal_form(), range);
+        }
+
+        let a: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        let b: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<=2.0.0".parse().unwrap();
+        assert!(!a.is_equivalent(&b).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_version_format_per_scheme() {
+        for scheme in schemes::supported_schemes() {
+            for garbage in ["not a version", "1..2", "-1", "1.2.3.4.x!"] {
+                match schemes::validate(scheme, garbage).unwrap_err() {
+                    VersError::InvalidVersionFormat(tag, input, message) => {
+                        assert!(tag.split('/').any(|t| t == *scheme), "tag {} for scheme {}", tag, scheme);
+                        assert_eq!(input, garbage);
+                        assert!(!message.is_empty());
+                    }
+                    e => panic!("unexpected error for {} in scheme {}: {:?}", garbage, scheme, e),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_nearest_boundary() {
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        let nearest = |v: &str| {
+            range.nearest_boundary(&v.parse().unwrap()).map(|(c, v)| format!("{}{}", c, v))
+        };
+        assert_eq!(nearest("1.9.9"), Some("<2.0.0".to_string()));
+        assert_eq!(nearest("2.0.1"), Some("<2.0.0".to_string()));
+        assert_eq!(nearest("2.0.0"), Some("<2.0.0".to_string()));
+        assert_eq!(nearest("0.9.0"), Some(">=1.0.0".to_string()));
+        assert_eq!(nearest("1.0.0"), Some(">=1.0.0".to_string()));
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/*".parse().unwrap();
+        assert_eq!(range.nearest_boundary(&"1.0.0".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn test_to_disjoint_normal_form() {
+        use std::ops::Bound::*;
+        let v = |s: &str| -> SemVer { s.parse().unwrap() };
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0|!=1.5.0".parse().unwrap();
+        assert_eq!(
+            range.to_disjoint_normal_form(),
+            vec![
+                (Included(v("1.0.0")), Excluded(v("1.5.0"))),
+                (Excluded(v("1.5.0")), Excluded(v("2.0.0"))),
+            ]
+        );
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/!=1.0.0|1.5.0|>=2.0.0".parse().unwrap();
+        assert_eq!(
+            range.to_disjoint_normal_form(),
+            vec![
+                (Included(v("1.5.0")), Included(v("1.5.0"))),
+                (Included(v("2.0.0")), Unbounded),
+            ]
+        );
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/!=1.0.0".parse().unwrap();
+        assert_eq!(
+            range.to_disjoint_normal_form(),
+            vec![(Unbounded, Excluded(v("1.0.0"))), (Excluded(v("1.0.0")), Unbounded)]
+        );
+    }
+
+    #[test]
+    fn test_set_operators() {
+        let a: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        let b: GenericVersionRange<SemVer> = "vers:npm/>=1.5.0|<3.0.0".parse().unwrap();
+
+        assert_eq!((&a & &b).to_string(), "vers:npm/>=1.5.0|<2.0.0");
+        assert_eq!((&a | &b).to_string(), "vers:npm/>=1.0.0|<3.0.0");
+        assert_eq!(a.clone() & b.clone(), a.intersect(&b).unwrap());
+        assert_eq!(a | b.clone(), "vers:npm/>=1.0.0|<3.0.0");
+        assert_eq!(b.clone() | b.clone(), b);
+    }
+
+    #[test]
+    #[should_panic(expected = "same versioning scheme")]
+    fn test_set_operators_scheme_mismatch() {
+        let a: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0".parse().unwrap();
+        let b: GenericVersionRange<SemVer> = "vers:semver/>=1.0.0".parse().unwrap();
+        let _ = &a & &b;
+    }
+
+    #[test]
+    fn test_from_npm_range() {
+        let npm = |s: &str| GenericVersionRange::<SemVer>::from_npm_range(s).unwrap().to_string();
+
+        // ^0.0.x only allows the exact patch
+        assert_eq!(npm("^0.0.0"), "vers:npm/>=0.0.0|<0.0.1");
+        assert_eq!(npm("^0.0.3"), "vers:npm/>=0.0.3|<0.0.4");
+        assert_eq!(npm("^0.0.3-beta"), "vers:npm/>=0.0.3-beta|<0.0.4");
+
+        // ^0.x.y allows patch changes
+        assert_eq!(npm("^0.1.0"), "vers:npm/>=0.1.0|<0.2.0");
+        assert_eq!(npm("^0.2.3"), "vers:npm/>=0.2.3|<0.3.0");
+        assert_eq!(npm("^0.10.0"), "vers:npm/>=0.10.0|<0.11.0");
+
+        // ^x.y.z allows minor and patch changes
+        assert_eq!(npm("^1.0.0"), "vers:npm/>=1.0.0|<2.0.0");
+        assert_eq!(npm("^1.2.3"), "vers:npm/>=1.2.3|<2.0.0");
+        assert_eq!(npm("^10.0.1"), "vers:npm/>=10.0.1|<11.0.0");
+        assert_eq!(npm("^1.2.3-beta.4"), "vers:npm/>=1.2.3-beta.4|<2.0.0");
+        assert_eq!(npm("^1.2.3+build.1"), "vers:npm/>=1.2.3|<2.0.0");
+
+        // Tilde
+        assert_eq!(npm("~1.2.3"), "vers:npm/>=1.2.3|<1.3.0");
+        assert_eq!(npm("~0.0.3"), "vers:npm/>=0.0.3|<0.1.0");
+
+        // Round trip through the compact form
+        for compact in ["^0.0.3", "^0.2.3", "^1.2.3", "^1.2.3-beta.4", "~1.2.3"] {
+            let range = GenericVersionRange::<SemVer>::from_npm_range(compact).unwrap();
+            assert_eq!(range.to_npm_range(), Some(compact.to_string()));
+        }
+
+        let range = GenericVersionRange::<SemVer>::from_npm_range("^1.2.3-beta.4").unwrap();
+        assert!(range.contains(&"1.2.3-beta.5".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"1.2.3-beta.3".parse().unwrap()).unwrap());
+        assert!(range.contains(&"1.9.0".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"2.0.0".parse().unwrap()).unwrap());
+
+        // Build metadata does not raise the lower bound above its release
+        let range = GenericVersionRange::<SemVer>::from_npm_range("^1.2.3+build.1").unwrap();
+        assert!(range.contains(&"1.2.3".parse().unwrap()).unwrap());
+        assert!(range.contains(&"1.2.3+build.2".parse().unwrap()).unwrap());
+
+        for invalid in ["1.2.3", ">=1.2.3", "^1.2", "^x", "^18446744073709551615.0.0"] {
+            assert!(GenericVersionRange::<SemVer>::from_npm_range(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_contains_owned() {
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+        assert!(range.contains_owned("1.5.0".parse().unwrap()).unwrap());
+        assert!(!range.contains_owned("2.0.0".parse().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_versions_stored_canonically() {
+        // SemVer pads partial versions
+        let range = parse("vers:npm/>=1.0|<2").unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<2.0.0");
+        assert_eq!(range, parse("vers:npm/>=1.0.0|<2.0.0").unwrap());
+        assert!(matches!(
+            parse("vers:npm/1.0|1.0.0").unwrap_err(),
+            VersError::DuplicateVersion(v) if v == "1.0.0"
+        ));
+
+        // Numeric versions drop leading zeros of their segments
+        let range = parse("vers:numeric/>=01.002|<3.010").unwrap();
+        assert_eq!(range.to_string(), "vers:numeric/>=1.2|<3.10");
+        assert!(matches!(parse("vers:numeric/01.2|1.2").unwrap_err(), VersError::DuplicateVersion(_)));
+
+        for scheme in schemes::supported_schemes() {
+            let version = schemes::example_version(scheme).unwrap();
+            let range = parse(&format!("vers:{}/{}", scheme, version)).unwrap();
+            assert_eq!(range.constraints()[0].version.to_string(), version);
+        }
+    }
+
+    #[test]
+    fn test_smallest_covering_caret() {
+        let caret = |s: &str| s.parse::<GenericVersionRange<SemVer>>().unwrap().smallest_covering_caret();
+
+        // Exactly a caret or tilde
+        assert_eq!(caret("vers:npm/>=1.2.3|<2.0.0"), Some("^1.2.3".to_string()));
+        assert_eq!(caret("vers:npm/>=1.2.3|<1.3.0"), Some("~1.2.3".to_string()));
+        assert_eq!(caret("vers:npm/>=0.0.3|<0.0.4"), Some("^0.0.3".to_string()));
+
+        // Within a caret or tilde
+        assert_eq!(caret("vers:npm/>=1.2.3|!=1.2.5|<1.2.8"), Some("~1.2.3".to_string()));
+        assert_eq!(caret("vers:npm/>1.2.3|<=1.5.0"), Some("^1.2.3".to_string()));
+        assert_eq!(caret("vers:npm/1.2.3|1.4.0"), Some("^1.2.3".to_string()));
+        assert_eq!(caret("vers:npm/>=0.2.3|<0.2.9"), Some("^0.2.3".to_string()));
+        assert_eq!(caret("vers:npm/0.0.3"), Some("^0.0.3".to_string()));
+        assert_eq!(caret("vers:npm/>=0.0.3|<0.0.9"), Some("~0.0.3".to_string()));
+
+        // Neither fits
+        assert_eq!(caret("vers:npm/>=1.2.3|<=2.0.0"), None);
+        assert_eq!(caret("vers:npm/>=0.2.3|<0.4.0"), None);
+        assert_eq!(caret("vers:npm/>=1.2.3"), None);
+        assert_eq!(caret("vers:npm/<1.2.3"), None);
+        assert_eq!(caret("vers:npm/*"), None);
+    }
+
+    #[test]
+    fn test_exclusion_precedes_inclusive_boundary() {
+        let c = |s: &str| -> VersionConstraint<SemVer> { VersionConstraint::parse(s).unwrap() };
+        let v: SemVer = "1.0.0".parse().unwrap();
+
+        assert!(matches!(
+            "vers:npm/>=1.0.0|!=1.0.0".parse::<GenericVersionRange<SemVer>>().unwrap_err(),
+            VersError::DuplicateVersion(_)
+        ));
+
+        // Ranges built without normalization may hold both constraints on a version
+        for constraints in [
+            vec![c(">=1.0.0"), c("!=1.0.0")],
+            vec![c("!=1.0.0"), c(">=1.0.0")],
+            vec![c("<=1.0.0"), c("!=1.0.0")],
+            vec![c("1.0.0"), c("!=1.0.0")],
+        ] {
+            let range = GenericVersionRange::new("npm".to_string(), constraints);
+            assert!(!range.contains(&v).unwrap(), "{}", range);
+        }
+
+        let range = GenericVersionRange::new("npm".to_string(), vec![c(">=1.0.0"), c("!=1.0.0")]);
+        assert!(range.contains(&"1.0.1".parse().unwrap()).unwrap());
+        assert!(!range.contains(&"0.9.0".parse().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_is_empty_prerelease_policy() {
+        use crate::schemes::numeric::NumericVersion;
+
+        let range = |s: &str| s.parse::<GenericVersionRange<SemVer>>().unwrap();
+
+        let mut only_prereleases = range("vers:npm/>1.0.0|<1.0.1");
+        assert!(only_prereleases.is_empty());
+        assert!(!only_prereleases.contains(&"1.0.1-alpha".parse().unwrap()).unwrap());
+        only_prereleases.allow_prereleases(true);
+        assert!(!only_prereleases.is_empty());
+        assert!(only_prereleases.contains(&"1.0.1-alpha".parse().unwrap()).unwrap());
+
+        assert!(!range("vers:npm/>=1.0.0|<1.0.1").is_empty());
+        assert!(!range("vers:npm/>1.0.0|<=1.0.1").is_empty());
+        assert!(!range("vers:npm/>1.0.0|<1.0.2").is_empty());
+        assert!(!range("vers:npm/>1.0.0|<1.0.1|>=2.0.0-rc.1|<2.0.0").is_empty());
+        assert!(range("vers:npm/>1.0.0|<1.0.1|>1.5.0|<1.5.1").is_empty());
+        assert!(!range("vers:npm/>=1.0.0-rc.1|<1.0.0").is_empty());
+        assert!(!range("vers:npm/>1.0.0").is_empty());
+
+        // Numeric versions have no pre-releases
+        let numeric: GenericVersionRange<NumericVersion> = "vers:numeric/>1.0|<1.0.1".parse().unwrap();
+        assert!(!numeric.is_empty());
+    }
+
+    #[test]
+    fn test_dedupe_canonical() {
+        let unique = dedupe_canonical(&["vers:npm/>=1.0.0|<2.0.0", "vers:npm/ <2.0.0 | >=1.0.0 "]).unwrap();
+        assert_eq!(unique, vec!["vers:npm/>=1.0.0|<2.0.0".to_string()]);
+
+        let unique = dedupe_canonical(&["vers:npm/2.0.0", "vers:npm/1.0.0", "vers:NPM/2.0.0", "vers:semver/1.0.0"]).unwrap();
+        assert_eq!(unique, vec!["vers:npm/2.0.0", "vers:npm/1.0.0", "vers:semver/1.0.0"]);
+
+        assert!(dedupe_canonical(&[]).unwrap().is_empty());
+        assert!(matches!(
+            dedupe_canonical(&["vers:npm/1.0.0", "vers:pypi/1.0"]).unwrap_err(),
+            VersError::UnsupportedVersioningSchemeWithHint { .. }
+        ));
+
+        let results = canonicalize_many(&["vers:npm/ 1.0.0 ", "not-vers", "vers:numeric/>=1.0"]);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], Ok("vers:npm/1.0.0".to_string()));
+        assert_eq!(results[1], Err(VersError::InvalidScheme));
+        assert_eq!(results[2], Ok("vers:numeric/>=1.0".to_string()));
+    }
+
+    #[test]
+    fn test_relation_to() {
+        use crate::range::RangeRelation::*;
+        let relation = |a: &str, b: &str| {
+            let a: GenericVersionRange<SemVer> = format!("vers:npm/{}", a).parse().unwrap();
+            let b: GenericVersionRange<SemVer> = format!("vers:npm/{}", b).parse().unwrap();
+            a.relation_to(&b).unwrap()
+        };
+
+        assert_eq!(relation(">=1.0.0|<2.0.0", ">=2.0.0"), Disjoint);
+        assert_eq!(relation("1.5.0", ">=1.0.0|!=1.5.0|<2.0.0"), Disjoint);
+        assert_eq!(relation(">=1.0.0|<2.0.0", "<2.0.0|>=1.0.0"), Equal);
+        assert_eq!(relation("*", "*"), Equal);
+        assert_eq!(relation(">=1.2.0|<1.5.0", ">=1.0.0|<2.0.0"), Subset);
+        assert_eq!(relation(">=1.0.0|!=1.5.0|<2.0.0", ">=1.0.0|<2.0.0"), Subset);
+        assert_eq!(relation("*", "1.0.0|2.0.0"), Superset);
+        assert_eq!(relation(">=1.0.0|<2.0.0", ">=1.5.0|<3.0.0"), Overlapping);
+        assert_eq!(relation("<1.0.0|>=2.0.0", ">=0.5.0|<2.5.0"), Overlapping);
+
+        let a: GenericVersionRange<SemVer> = "vers:npm/*".parse().unwrap();
+        let b: GenericVersionRange<SemVer> = "vers:semver/*".parse().unwrap();
+        assert!(matches!(a.relation_to(&b).unwrap_err(), VersError::IncompatibleVersioningSchemes(..)));
+    }
+
+    #[test]
+    fn test_constraint_separator() {
+        let semicolon = ParseOptions { constraint_separator: ';', ..Default::default() };
+
+        let range = DynamicVersionRange::from_str_with_options("vers:npm/>=1.0.0;<2.0.0", &semicolon).unwrap();
+        assert_eq!(range, parse("vers:npm/>=1.0.0|<2.0.0").unwrap());
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<2.0.0");
+        let range = DynamicVersionRange::from_str_with_options("vers:npm/*", &semicolon).unwrap();
+        assert_eq!(range.to_string(), "vers:npm/*");
+
+        // Only the configured separator splits constraints
+        assert!(DynamicVersionRange::from_str_with_options("vers:npm/>=1.0.0|<2.0.0", &semicolon).is_err());
+        assert!(parse("vers:npm/>=1.0.0;<2.0.0").is_err());
+        assert!(parse("vers:npm/>=1.0.0|<2.0.0").is_ok());
+    }
+
+    #[test]
+    fn test_scheme_type_mismatch() {
+        use crate::schemes::numeric::NumericVersion;
+        use crate::schemes::opaque::OpaqueVersion;
+
+        for (scheme, input) in [("maven", "vers:maven/>=1.0.0|<2.0.0"), ("pypi", "vers:pypi/*"), ("numeric", "vers:numeric/1.0.0")] {
+            assert_eq!(
+                input.parse::<GenericVersionRange<SemVer>>().unwrap_err(),
+                VersError::IncompatibleVersioningSchemes(scheme.to_string(), "semver/npm".to_string())
+            );
+        }
+        assert!(matches!(
+            "vers:npm/1.0".parse::<GenericVersionRange<NumericVersion>>().unwrap_err(),
+            VersError::IncompatibleVersioningSchemes(..)
+        ));
+        assert!(matches!(
+            GenericVersionRange::<SemVer>::try_from_parts("maven".to_string(), vec![]).unwrap_err(),
+            VersError::IncompatibleVersioningSchemes(..)
+        ));
+
+        // Version types without scheme restriction suit any scheme
+        assert!("vers:maven/1.0".parse::<GenericVersionRange<OpaqueVersion>>().is_ok());
+        assert!("vers:semver/1.0.0".parse::<GenericVersionRange<SemVer>>().is_ok());
+    }
+
+    #[test]
+    fn test_debug_interval_notation() {
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|!=1.5.0|<2.0.0".parse().unwrap();
+        assert_eq!(format!("{:?}", range), "npm: [1.0.0, 2.0.0) \\ {1.5.0}");
+        assert!(format!("{:#?}", range).contains("constraints"));
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/1.0.0|>2.0.0".parse().unwrap();
+        assert_eq!(format!("{:?}", range), "npm: {1.0.0} ∪ (2.0.0, +∞)");
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/!=1.0.0".parse().unwrap();
+        assert_eq!(format!("{:?}", range), "npm: (-∞, +∞) \\ {1.0.0}");
+    }
+
+    #[test]
+    fn test_percent_encoded_comma_round_trip() {
+        use crate::schemes::opaque::OpaqueVersion;
+
+        let range: GenericVersionRange<OpaqueVersion> = "vers:maven/%5B1.0%2C2.0%29|!=1.5".parse().unwrap();
+        assert_eq!(range.constraints.len(), 2);
+        let versions: Vec<String> = range.constraints.iter().map(|c| c.version.to_string()).collect();
+        assert!(versions.contains(&"[1.0,2.0)".to_string()));
+
+        // The portable form encodes the comma, and both forms parse back to the same range
+        assert_eq!(format!("{:#}", range), "vers:maven/!=1.5|%5B1.0%2C2.0%29");
+        assert_eq!(format!("{:#}", range).parse::<GenericVersionRange<OpaqueVersion>>().unwrap(), range);
+        assert_eq!(range.to_string().parse::<GenericVersionRange<OpaqueVersion>>().unwrap(), range);
+
+        // Only the constraint separator splits constraints, never a comma
+        let range: GenericVersionRange<OpaqueVersion> = "vers:maven/1.0,2.0".parse().unwrap();
+        assert_eq!(range.constraints.len(), 1);
+        assert_eq!(range.constraints[0].version.to_string(), "1.0,2.0");
+
+        // Versions of ordered schemes reject a decoded comma
+        assert!("vers:npm/1.0.0%2C2.0.0".parse::<GenericVersionRange<SemVer>>().is_err());
+    }
+
+    #[test]
+    fn test_parse_structural() {
+        let range = DynamicVersionRange::parse_structural("vers:pypi/>=1.0.0|<2.0.0").unwrap();
+        assert_eq!(range.versioning_scheme, "pypi");
+        assert_eq!(range.constraints, vec![">=1.0.0", "<2.0.0"]);
+        assert_eq!(range.to_string(), "vers:pypi/>=1.0.0|<2.0.0");
+        let error = range.contains("1.5.0").unwrap_err();
+        assert_eq!(error, VersError::MembershipUnsupported("pypi".to_string()));
+        assert_eq!(
+            error.to_string(),
+            "Structural ranges cannot evaluate membership, versions of scheme pypi are not compared"
+        );
+        // Also for schemes supported by typed ranges
+        let range = DynamicVersionRange::parse_structural("vers:npm/>=1.0.0").unwrap();
+        assert_eq!(
+            range.contains("1.5.0").unwrap_err().to_string(),
+            "Structural ranges cannot evaluate membership, versions of scheme npm are not compared"
+        );
+
+        assert!(DynamicVersionRange::parse_structural("vers:pypi/*").is_ok());
+        assert!(matches!(
+            DynamicVersionRange::parse_structural("vers:pypi/>=>=1.0").unwrap_err(),
+            VersError::ConstraintParseError { index: 0, .. }
+        ));
+        assert!(DynamicVersionRange::parse_structural("vers:pypi/*|1.0").is_err());
+        assert!(DynamicVersionRange::parse_structural("vers:pypi/").is_err());
+        assert!(DynamicVersionRange::parse_structural("pypi/1.0").is_err());
+    }
+
+    #[test]
+    fn test_conda_scheme() {
+        use crate::schemes::conda::CondaVersion;
+
+        let v = |s: &str| -> CondaVersion { s.parse().unwrap() };
+
+        // Ordering from the documentation of Conda's VersionOrder
+        let ordered = [
+            "0.4", "0.4.1.rc", "0.4.1", "0.5a1", "0.5b3", "0.5c1", "0.5", "0.9.6", "0.960923",
+            "1.0", "1.1dev1", "1.1a1", "1.1.0dev1", "1.1.a1", "1.1.0rc1", "1.1.0", "1.1.0post1",
+            "1.1post1", "1996.07.12", "1!0.4.1", "1!3.1.1.6", "2!0.4.1",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        assert!(v("1.0.1post1") > v("1.0.1"));
+        assert_eq!(v("1.1.dev1"), v("1.1.0dev1"));
+        assert_eq!(v("1.1.post1"), v("1.1.0post1"));
+        assert_eq!(v("1.1"), v("1.1.0"));
+        assert_eq!(v("1.0_1"), v("1.0.1"));
+        assert!(v("1.0+local.2") > v("1.0+local.1"));
+        assert_eq!(v("1.0RC1").to_string(), "1.0rc1");
+
+        for invalid in ["", "1..0", "1.0-1", "a!1.0", "1!2!3", "1.0+a+b", "1.*"] {
+            assert!(
+                matches!(invalid.parse::<CondaVersion>(), Err(VersError::InvalidVersionFormat("conda", ..))),
+                "{}",
+                invalid
+            );
+        }
+
+        let range = parse("vers:conda/>=1.0.1|<2.0").unwrap();
+        assert!(matches!(range, DynamicVersionRange::Conda(_)));
+        assert!(range.contains("1.0.1post1").unwrap());
+        assert!(!range.contains("1.0.1rc1").unwrap());
+        assert!(schemes::validate("conda", "1!2.0.post1").is_ok());
+    }
+
+    #[test]
+    fn test_conda_wildcard() {
+        let range = parse("vers:conda/1.2.*").unwrap();
+        assert_eq!(range.to_string(), "vers:conda/>=1.2|<1.3");
+        assert!(range.contains("1.2.7").unwrap());
+        assert!(!range.contains("1.3").unwrap());
+
+        assert_eq!(parse("vers:conda/=1!2.*|3.0").unwrap().to_string(), "vers:conda/3.0|>=1!2|<1!3");
+        assert!(matches!(
+            parse("vers:conda/>=1.2.*").unwrap_err(),
+            VersError::ConstraintParseError { index: 0, .. }
+        ));
+        assert!(parse("vers:conda/1.2+local.*").is_err());
+
+        // Other schemes do not support wildcards
+        assert!(parse("vers:npm/1.2.*").is_err());
+    }
+
+    #[test]
+    fn test_prepared_range_shared_across_threads() {
+        use crate::range::prepared::PreparedRange;
+        use crate::schemes::opaque::OpaqueVersion;
+        use std::thread;
+
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PreparedRange<SemVer>>();
+
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|!=1.5.0|<2.0.0".parse().unwrap();
+        let prepared = range.clone().into_prepared().unwrap();
+        assert_eq!(prepared.range(), &range);
+
+        let handles: Vec<_> = ["0.9.0", "1.0.0", "1.5.0", "1.9.9", "2.0.0"]
+            .into_iter()
+            .map(|version| {
+                let prepared = prepared.clone();
+                thread::spawn(move || prepared.contains(&version.parse().unwrap()).unwrap())
+            })
+            .collect();
+        let results: Vec<bool> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(results, vec![false, true, false, true, false]);
+
+        // Ranges whose versions cannot be ordered fail when prepared
+        let opaque = GenericVersionRange::new("opaque".to_string(), vec![
+            VersionConstraint::new(Comparator::GreaterThan, "a".parse::<OpaqueVersion>().unwrap()),
+        ]);
+        assert!(matches!(opaque.into_prepared(), Err(VersError::OrderingUnsupported(_))));
+    }
+
+    #[test]
+    fn test_implicit_equal_after_first_constraint() {
+        use crate::range::CanonicalViolation;
+
+        // A bare version anywhere in the list is an implicit "="
+        let constraints: Vec<VersionConstraint<SemVer>> = ">=1.0.0|1.5.0|<2.0.0"
+            .split('|')
+            .map(|c| VersionConstraint::parse(c).unwrap())
+            .collect();
+        assert_eq!(constraints[1], VersionConstraint::parse("=1.5.0").unwrap());
+
+        // As written, "=1.5.0" followed by "<2.0.0" violates the canonical form
+        let unnormalized = GenericVersionRange::new("npm".to_string(), constraints);
+        assert_eq!(
+            unnormalized.check_canonical(),
+            Err(vec![CanonicalViolation::DisallowedAfterEqual { index: 2 }])
+        );
+
+        // Normalization simplifies first, folding the redundant "=" into the interval, so
+        // that implicit and explicit forms parse to the same valid range
+        let implicit: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|1.5.0|<2.0.0".parse().unwrap();
+        let explicit: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|=1.5.0|<2.0.0".parse().unwrap();
+        assert_eq!(implicit, explicit);
+        assert_eq!(implicit.to_string(), "vers:npm/>=1.0.0|<2.0.0");
+        assert!(implicit.check_canonical().is_ok());
+        assert!(implicit.contains(&"1.5.0".parse().unwrap()).unwrap());
+
+        // A "=" outside the intervals is kept, and followed by a lower bound as required
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=2.0.0|1.5.0|<3.0.0".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/1.5.0|>=2.0.0|<3.0.0");
+        assert!(range.check_canonical().is_ok());
+    }
+
+    #[test]
+    fn test_shared_bounds_preserve_membership() {
+        let range = |s: &str| s.parse::<GenericVersionRange<SemVer>>().unwrap();
+        let contains = |range: &GenericVersionRange<SemVer>, v: &str| range.contains(&v.parse().unwrap()).unwrap();
+
+        // A run of lower bounds starts at its first bound, so the later one is redundant
+        let merged = range("vers:npm/<1.0.0|>=1.0.0|>=2.0.0");
+        assert_eq!(merged.to_string(), "vers:npm/*");
+        for version in ["0.5.0", "1.0.0", "1.5.0", "2.5.0"] {
+            assert!(contains(&merged, version), "{}", version);
+        }
+
+        // A run of upper bounds ends at its last bound
+        assert_eq!(canonicalize("vers:npm/<1.0.0|>=1.0.0|<0.5.0").unwrap(), "vers:npm/*");
+
+        // Bounds on the same version are read in the order written
+        let point = range("vers:npm/>=1.0.0|<=1.0.0");
+        assert_eq!(point.to_string(), "vers:npm/1.0.0");
+        assert!(contains(&point, "1.0.0"));
+        assert!(!contains(&point, "0.5.0") && !contains(&point, "1.5.0"));
+        let all = range("vers:npm/<=1.0.0|>=1.0.0");
+        assert_eq!(all.to_string(), "vers:npm/*");
+        assert!(contains(&all, "0.5.0") && contains(&all, "1.0.0") && contains(&all, "1.5.0"));
+
+        // Other constraints on the same version remain duplicates
+        for input in ["vers:npm/>=1.0.0|>1.0.0", "vers:npm/1.0.0|<=1.0.0", "vers:npm/>=1.0.0|<=1.0.0|!=1.0.0"] {
+            assert!(matches!(
+                input.parse::<GenericVersionRange<SemVer>>().unwrap_err(),
+                VersError::DuplicateVersion(_)
+            ), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_normalization_keeps_prerelease_anchors() {
+        let v = |s: &str| -> SemVer { s.parse().unwrap() };
+
+        // The "=" on a pre-release lies within the interval, but still admits pre-releases
+        let range: GenericVersionRange<SemVer> = "vers:npm/<=1.5.0|1.5.0-rc.1".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/1.5.0-rc.1|<=1.5.0");
+        assert!(range.contains(&v("1.5.0-rc.1")).unwrap());
+        assert!(range.contains(&v("1.5.0-beta")).unwrap());
+        assert!(!range.contains(&v("1.4.0-beta")).unwrap());
+        assert_eq!(range.to_string().parse::<GenericVersionRange<SemVer>>().unwrap(), range);
+
+        // Redundant bounds on pre-releases keep admitting them as well
+        let range: GenericVersionRange<SemVer> = "vers:npm/<2.0.0-rc.1|<3.0.0".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/2.0.0-rc.1|<3.0.0");
+        assert!(range.contains(&v("2.0.0-beta")).unwrap());
+
+        // Anchors are only kept for pre-releases still contained, and not duplicated
+        let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0-rc.1|>=1.0.0-rc.2|<2.0.0".parse().unwrap();
+        assert_eq!(range.to_string(), "vers:npm/>=1.0.0-rc.1|<2.0.0");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+//! Parse options for the vers-rs library.
+//!
+//! The `ParseOptions` struct controls how version range specifiers are parsed.
+//! Plain `FromStr` parsing uses the default options.
+
+/// Options controlling how version range specifiers are parsed.
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::ParseOptions;
+/// use vers_rs::range::dynamic::DynamicVersionRange;
+///
+/// let options = ParseOptions { strict: true, ..Default::default() };
+/// assert!(DynamicVersionRange::from_str_with_options("vers:npm/*|", &options).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Reject malformed but recoverable input instead of tolerating it.
+    ///
+    /// In strict mode, empty constraints between pipes (e.g. `*|`, `|*` or
+    /// `>=1.0.0||<2.0.0`) are rejected instead of being ignored.
+    pub strict: bool,
+
+    /// The maximum number of constraints a specifier may contain, or `None` for no limit.
+    ///
+    /// Set this when parsing untrusted input to bound the work spent on a single specifier.
+    /// Exceeding the limit fails with `VersError::TooManyConstraints`.
+    pub max_constraints: Option<usize>,
+
+    /// Restrict the range to stable releases by rounding pre-release constraint versions.
+    ///
+    /// A pre-release precedes its release, so the stable versions above `1.0.0-rc.1` are
+    /// those from `1.0.0` on, and the stable versions below it are those below `1.0.0`:
+    /// `>` and `>=` on a pre-release become `>=` on its release, and `<` and `<=` become `<`
+    /// on its release. `=` and `!=` on a pre-release are rejected with
+    /// `VersError::InvalidConstraint`, as they concern no stable version.
+    pub exclude_prereleases: bool,
+
+    /// Percent-decode constraint versions before parsing them (the default).
+    ///
+    /// With decoding off, a version such as `1.0.0%2Bbuild` is passed to the version type
+    /// literally, which keeps it unaltered for schemes accepting it and fails with
+    /// `VersError::InvalidConstraint` for schemes rejecting it.
+    ///
+    /// Such ranges do not round-trip through `Display`: it percent-encodes the stored
+    /// version like any other, so `vers:generic/1.0.0%2Bbuild` is written as
+    /// `vers:generic/1.0.0%252Bbuild`, which parses back to the same range with decoding on.
+    pub decode_percent: bool,
+
+    /// The character separating constraints, `|` by default as in the vers specification.
+    ///
+    /// Set this to ingest ranges of non-conforming producers, e.g. `;` for
+    /// `vers:npm/>=1.0.0;<2.0.0`. Only the given character separates constraints, and
+    /// `Display` always writes `|`.
+    pub constraint_separator: char,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            max_constraints: None,
+            exclude_prereleases: false,
+            decode_percent: true,
+            constraint_separator: '|',
+        }
+    }
+}
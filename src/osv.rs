@@ -0,0 +1,222 @@
+//! OSV interoperability for the vers-rs library.
+//!
+//! [OSV](https://ossf.github.io/osv-schema/) advisories express affected version ranges
+//! as a sequence of `introduced`, `fixed` and `last_affected` events. This module converts
+//! such events into version ranges and back.
+
+use crate::comparator::Comparator::*;
+use crate::range::interval;
+use crate::schemes::semver::SemVer;
+use crate::{GenericVersionRange, VersError, VersionConstraint};
+use std::ops::Bound;
+use std::ops::Bound::*;
+
+/// An event of an OSV affected range.
+///
+/// Event versions are kept as strings, as in the OSV schema, where the introduced
+/// version `"0"` denotes the lowest possible version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OsvEvent {
+    /// The version introducing the vulnerability
+    Introduced(String),
+    /// The version fixing the vulnerability
+    Fixed(String),
+    /// The last version affected by the vulnerability
+    LastAffected(String),
+}
+
+/// A summary of a range of affected versions, as rendered in a security advisory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VulnSummary {
+    /// The intervals of affected versions in ascending order, ignoring `excluded` versions
+    pub affected_intervals: Vec<(Bound<SemVer>, Bound<SemVer>)>,
+    /// The minimum unaffected version above each interval that is bounded above
+    pub fixed_in: Vec<SemVer>,
+    /// The versions excluded from the affected intervals
+    pub excluded: Vec<SemVer>,
+}
+
+/// Get the minimum version above an interval with the given upper bound.
+fn fixed_version(upper: Bound<SemVer>) -> Option<SemVer> {
+    match upper {
+        Included(v) => Some(v.increment_patch()),
+        Excluded(v) => Some(v),
+        Unbounded => None,
+    }
+}
+
+impl GenericVersionRange<SemVer> {
+    /// Create a version range from the events of a single OSV affected range.
+    ///
+    /// `introduced` maps to a `>=` constraint, `fixed` to a `<` constraint and
+    /// `last_affected` to a `<=` constraint. A missing `introduced` event (or the OSV
+    /// convention `"0"`) means the range starts at the lowest version, and a missing
+    /// `fixed` and `last_affected` event means the range is open-ended.
+    ///
+    /// # Arguments
+    ///
+    /// * `versioning_scheme` - The versioning scheme of the range (e.g., "npm")
+    /// * `introduced` - The version introducing the vulnerability
+    /// * `fixed` - The version fixing the vulnerability
+    /// * `last_affected` - The last version affected by the vulnerability
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the normalized range or an error if a version is invalid,
+    /// both `fixed` and `last_affected` are given, or the range would be empty
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range = GenericVersionRange::<SemVer>::from_osv_events(
+    ///     "npm", Some("1.0.0"), Some("1.5.0"), None,
+    /// ).unwrap();
+    /// assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<1.5.0");
+    /// ```
+    pub fn from_osv_events(
+        versioning_scheme: &str,
+        introduced: Option<&str>,
+        fixed: Option<&str>,
+        last_affected: Option<&str>,
+    ) -> Result<Self, VersError> {
+        let mut constraints = Vec::new();
+
+        if let Some(introduced) = introduced.filter(|v| *v != "0") {
+            constraints.push(VersionConstraint::new(GreaterThanOrEqual, introduced.parse()?));
+        }
+
+        match (fixed, last_affected) {
+            (Some(_), Some(_)) => {
+                return Err(VersError::InvalidRange(
+                    "OSV events must not contain both \"fixed\" and \"last_affected\"".to_string(),
+                ));
+            }
+            (Some(fixed), None) => {
+                constraints.push(VersionConstraint::new(LessThan, fixed.parse()?));
+            }
+            (None, Some(last_affected)) => {
+                constraints.push(VersionConstraint::new(LessThanOrEqual, last_affected.parse()?));
+            }
+            (None, None) => {}
+        }
+
+        // The introducing version must precede the end of the range
+        if let [lower, upper] = constraints.as_slice()
+            && !upper.matches(&lower.version) {
+            return Err(VersError::InvalidRange(format!(
+                "OSV \"introduced\" version {} is not below the end of the range {}",
+                lower.version,
+                upper.version,
+            )));
+        }
+
+        if constraints.is_empty() {
+            constraints.push(VersionConstraint::new(Any, SemVer::default()));
+        }
+
+        Self::try_from_parts(versioning_scheme.to_lowercase(), constraints)
+    }
+
+    /// Convert this range into OSV affected-range events.
+    ///
+    /// Every interval of the range yields an `introduced` event, followed by a `fixed` event
+    /// for an exclusive upper bound or a `last_affected` event for an inclusive upper bound.
+    /// Open-ended intervals have no closing event. `!=` exclusions split their interval in two.
+    /// OSV cannot express exclusive lower bounds, so those of `>` bounds and of the intervals
+    /// following an exclusion become an `introduced` event at the smallest version above the
+    /// bound (see `SemVer::successor`). The range is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// The list of OSV events, grouped by interval in ascending version order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::osv::OsvEvent;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<1.5.0".parse().unwrap();
+    /// assert_eq!(range.to_osv_events(), vec![
+    ///     OsvEvent::Introduced("1.0.0".to_string()),
+    ///     OsvEvent::Fixed("1.5.0".to_string()),
+    /// ]);
+    /// ```
+    pub fn to_osv_events(&self) -> Vec<OsvEvent> {
+        let mut events = Vec::new();
+        for (lower, upper) in interval::from_constraints(&self.constraints) {
+            events.push(OsvEvent::Introduced(match lower {
+                Included(v) => v.to_string(),
+                Excluded(v) => v.successor().to_string(),
+                Unbounded => "0".to_string(),
+            }));
+            match upper {
+                Included(v) => events.push(OsvEvent::LastAffected(v.to_string())),
+                Excluded(v) => events.push(OsvEvent::Fixed(v.to_string())),
+                Unbounded => {}
+            }
+        }
+        events
+    }
+
+    /// Get the minimum version above this range, i.e. the version fixing a vulnerability
+    /// whose affected versions this range represents.
+    ///
+    /// For an exclusive upper bound this is the bound version itself, and for an inclusive
+    /// upper bound it is the next patch release (see `SemVer::increment_patch`). The range
+    /// is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// The fixed version, or `None` if the range is empty or open-ended above
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<=1.5.0".parse().unwrap();
+    /// assert_eq!(range.minimum_unaffected().unwrap().to_string(), "1.5.1");
+    /// ```
+    pub fn minimum_unaffected(&self) -> Option<SemVer> {
+        fixed_version(interval::bounds(&self.constraints).pop()?.1)
+    }
+
+    /// Summarize this range as the affected versions of a security advisory.
+    ///
+    /// This aggregates `to_intervals`, the minimum unaffected version of every interval
+    /// (see `minimum_unaffected`) and `exclusions`. The range is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// The summary of the affected intervals, fixed versions and excluded versions
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<1.5.0|>=2.0.0|<=2.3.0".parse().unwrap();
+    /// let summary = range.vuln_summary();
+    /// assert_eq!(summary.affected_intervals.len(), 2);
+    /// assert_eq!(summary.fixed_in, vec!["1.5.0".parse().unwrap(), "2.3.1".parse().unwrap()]);
+    /// ```
+    pub fn vuln_summary(&self) -> VulnSummary {
+        let affected_intervals = self.to_intervals();
+        let fixed_in = affected_intervals
+            .iter()
+            .filter_map(|(_, upper)| fixed_version(upper.clone()))
+            .collect();
+        VulnSummary {
+            affected_intervals,
+            fixed_in,
+            excluded: self.exclusions().into_iter().cloned().collect(),
+        }
+    }
+}
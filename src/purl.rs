@@ -0,0 +1,75 @@
+//! Package URL interoperability for the vers-rs library.
+//!
+//! A [purl](https://github.com/package-url/purl-spec) can carry a version range in its
+//! `vers` qualifier, as in `pkg:npm/lodash?vers=vers:npm%2F%3E%3D1.0.0`. This module
+//! extracts and parses such ranges.
+
+use crate::range::VersionRange;
+use crate::schemes;
+use crate::{DynamicVersionRange, VersError};
+use percent_encoding::percent_decode_str;
+
+impl DynamicVersionRange {
+    /// Parse the version range held by the `vers` qualifier of a package URL.
+    ///
+    /// The qualifier value is percent-decoded and parsed as a vers string. If the purl
+    /// type is itself a known versioning scheme, it must be an alias of the range's scheme,
+    /// so that e.g. a `pkg:npm/...` purl cannot carry a `vers:numeric/...` range.
+    ///
+    /// # Arguments
+    ///
+    /// * `purl` - The package URL to extract the range from
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the parsed range or an error. A malformed purl or a
+    /// missing `vers` qualifier fails with `VersError::InvalidPurl`, and a purl type
+    /// conflicting with the range's scheme fails with `VersError::IncompatibleVersioningSchemes`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let range = DynamicVersionRange::from_purl_with_range(
+    ///     "pkg:npm/lodash?vers=vers%3Anpm%2F%3E%3D1.0.0%7C%3C2.0.0",
+    /// ).unwrap();
+    /// assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<2.0.0");
+    /// ```
+    pub fn from_purl_with_range(purl: &str) -> Result<Self, VersError> {
+        let invalid = |reason: &str| VersError::InvalidPurl(format!("{}: {}", reason, purl));
+
+        let rest = purl.trim().strip_prefix("pkg:").ok_or_else(|| invalid("Missing \"pkg:\" prefix"))?;
+        // The subpath follows the qualifiers and is irrelevant here
+        let rest = rest.split_once('#').map_or(rest, |(rest, _)| rest);
+        let (path, qualifiers) = rest.split_once('?').ok_or_else(|| invalid("Missing qualifiers"))?;
+
+        let purl_type = path
+            .trim_start_matches('/')
+            .split_once('/')
+            .map(|(purl_type, _)| purl_type)
+            .filter(|purl_type| !purl_type.is_empty())
+            .ok_or_else(|| invalid("Missing package type"))?
+            .to_lowercase();
+
+        let value = qualifiers
+            .split('&')
+            .filter_map(|qualifier| qualifier.split_once('='))
+            .find(|(key, _)| key.eq_ignore_ascii_case("vers"))
+            .map(|(_, value)| value)
+            .ok_or_else(|| invalid("Missing \"vers\" qualifier"))?;
+        let value = percent_decode_str(value)
+            .decode_utf8()
+            .map_err(|_| invalid("Invalid URL encoding in \"vers\" qualifier"))?;
+
+        let range: DynamicVersionRange = value.parse()?;
+        if let Some(purl_scheme) = schemes::canonical_scheme(&purl_type)
+            && purl_scheme != range.canonical_scheme() {
+            return Err(VersError::IncompatibleVersioningSchemes(
+                purl_type,
+                range.versioning_scheme().to_string(),
+            ));
+        }
+        Ok(range)
+    }
+}
@@ -1,9 +1,16 @@
 use crate::constraint::VT;
-use crate::range::VersionRange;
-use crate::schemes::semver::SemVer;
-use crate::{GenericVersionRange, VersError, VersionConstraint};
+use crate::range::structural::StructuralRange;
+use crate::range::{split_specifier, VersionRange};
+use crate::schemes;
+use crate::schemes::conda::CondaVersion;
+use crate::schemes::numeric::NumericVersion;
+use crate::schemes::semver::{SemVer, SEMVER_SCHEME};
+use crate::{GenericVersionRange, ParseOptions, VersError, VersionConstraint};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::ops::Bound;
 use std::str::FromStr;
 
 /// A dynamic version range that automatically detects the versioning scheme.
@@ -14,6 +21,8 @@ use std::str::FromStr;
 ///
 /// It currently supports the following schemes:
 /// - "semver" and "npm" schemes using SemVer version type
+/// - "numeric" scheme using NumericVersion version type
+/// - "conda" scheme using CondaVersion version type
 ///
 /// # Examples
 ///
@@ -33,6 +42,21 @@ use std::str::FromStr;
 pub enum DynamicVersionRange {
     /// SemVer-based range (for "semver" and "npm" schemes)
     SemVer(GenericVersionRange<SemVer>),
+    /// Range of dotted integer versions (for the "numeric" scheme)
+    Numeric(GenericVersionRange<NumericVersion>),
+    /// Range of Conda package versions (for the "conda" scheme)
+    Conda(GenericVersionRange<CondaVersion>),
+}
+
+/// Evaluate an expression on the typed range wrapped by any variant.
+macro_rules! with_range {
+    ($self:expr, $range:ident => $body:expr) => {
+        match $self {
+            DynamicVersionRange::SemVer($range) => $body,
+            DynamicVersionRange::Numeric($range) => $body,
+            DynamicVersionRange::Conda($range) => $body,
+        }
+    };
 }
 
 impl DynamicVersionRange {
@@ -41,34 +65,433 @@ impl DynamicVersionRange {
     /// This is a helper function used internally to determine which version type
     /// to use when parsing the range.
     fn extract_versioning_scheme(s: &str) -> Result<String, VersError> {
-        // Remove all spaces and tabs
-        let s = s.replace(|c: char| c.is_whitespace(), "");
+        let (versioning_scheme, _) = split_specifier(s)?;
+        Ok(versioning_scheme)
+    }
+
+    /// Parse a version range specifier string using the given parse options.
+    ///
+    /// This function automatically detects the versioning scheme and constructs
+    /// the appropriate typed version range. Plain `FromStr` parsing is equivalent
+    /// to using the default options.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The version range specifier string to parse
+    /// * `options` - The options controlling the parsing
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the parsed `DynamicVersionRange` or an error
+    pub fn from_str_with_options(s: &str, options: &ParseOptions) -> Result<Self, VersError> {
+        // Extract the versioning scheme first to determine which type to use
+        let versioning_scheme = Self::extract_versioning_scheme(s)?;
 
-        // Split on colon
-        let parts: Vec<&str> = s.splitn(2, ':').collect();
-        if parts.len() != 2 {
-            return Err(VersError::InvalidScheme);
+        match versioning_scheme.as_str() {
+            "semver" | "npm" => {
+                let range = GenericVersionRange::<SemVer>::from_str_with_options(s, options)?;
+                Ok(DynamicVersionRange::SemVer(range))
+            }
+            "numeric" => {
+                let range = GenericVersionRange::<NumericVersion>::from_str_with_options(s, options)?;
+                Ok(DynamicVersionRange::Numeric(range))
+            }
+            "conda" => {
+                let range = GenericVersionRange::<CondaVersion>::from_str_with_options(s, options)?;
+                Ok(DynamicVersionRange::Conda(range))
+            }
+            _ => Err(VersError::UnsupportedVersioningSchemeWithHint {
+                scheme: versioning_scheme,
+                supported: schemes::supported_schemes(),
+            }),
+        }
+    }
+
+    /// Parse a version range specifier string, first rewriting its versioning scheme through
+    /// an alias table.
+    ///
+    /// This allows ingesting data using synonyms of the supported schemes, such as "nodejs"
+    /// for "npm". The parsed range uses the rewritten scheme. Alias keys must be lowercase,
+    /// and schemes without an alias are parsed as-is.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The version range specifier string to parse
+    /// * `aliases` - The alias table, mapping scheme aliases to scheme names
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the parsed `DynamicVersionRange` or an error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    /// use vers_rs::range::VersionRange;
+    /// use vers_rs::schemes::default_scheme_aliases;
+    ///
+    /// let range = DynamicVersionRange::from_str_with_aliases("vers:nodejs/1.2.3", &default_scheme_aliases()).unwrap();
+    /// assert_eq!(range.versioning_scheme(), "npm");
+    /// ```
+    pub fn from_str_with_aliases(s: &str, aliases: &HashMap<String, String>) -> Result<Self, VersError> {
+        let (versioning_scheme, constraints) = split_specifier(s)?;
+        match aliases.get(&versioning_scheme) {
+            Some(scheme) => format!("vers:{}/{}", scheme, constraints).parse(),
+            None => s.parse(),
         }
+    }
+
+    /// Parse a version range from its versioning scheme and constraints string, as found
+    /// in configuration such as `npm:>=1.0.0|<2.0.0`.
+    ///
+    /// This is equivalent to parsing `vers:<scheme>/<constraints>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `scheme` - The versioning scheme (e.g., "npm", "semver")
+    /// * `constraints` - The constraints string, e.g. `>=1.0.0|<2.0.0`
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the parsed `DynamicVersionRange` or an error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let range = DynamicVersionRange::from_scheme_and_constraints("npm", ">=1.0.0|<2.0.0").unwrap();
+    /// assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<2.0.0");
+    /// ```
+    pub fn from_scheme_and_constraints(scheme: &str, constraints: &str) -> Result<Self, VersError> {
+        format!("vers:{}/{}", scheme, constraints).parse()
+    }
+
+    /// Parse a version range specifier string for its structure only, without parsing its
+    /// versions.
+    ///
+    /// This accepts any versioning scheme, including those not supported by
+    /// `DynamicVersionRange`, so that their vers strings can at least be validated.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The version range specifier string to parse
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the parsed `StructuralRange` or an error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let range = DynamicVersionRange::parse_structural("vers:pypi/>=1.0.0|<2.0.0").unwrap();
+    /// assert_eq!(range.constraints.len(), 2);
+    /// assert!(range.contains("1.5.0").is_err());
+    /// ```
+    pub fn parse_structural(s: &str) -> Result<StructuralRange, VersError> {
+        s.parse()
+    }
 
-        // Validate URI scheme
-        let scheme = parts[0];
-        if scheme != "vers" {
-            return Err(VersError::InvalidScheme);
+    /// Check if an already parsed SemVer version is contained within this range.
+    ///
+    /// This avoids parsing a version string when the caller already holds a typed version.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version to check
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a boolean indicating whether the version is in the range, or
+    /// `VersError::IncompatibleVersioningSchemes` if this range does not use SemVer versions
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let version: SemVer = "1.5.0".parse().unwrap();
+    /// assert!(range.contains_typed(&version).unwrap());
+    /// ```
+    pub fn contains_typed(&self, version: &SemVer) -> Result<bool, VersError> {
+        match self {
+            DynamicVersionRange::SemVer(range) => range.contains(version),
+            _ => Err(VersError::IncompatibleVersioningSchemes(
+                self.versioning_scheme().to_string(),
+                SEMVER_SCHEME.to_string(),
+            )),
         }
+    }
 
-        // Split on slash
-        let specifier_parts: Vec<&str> = parts[1].splitn(2, '/').collect();
-        if specifier_parts.len() != 2 {
-            return Err(VersError::MissingVersioningScheme);
+    /// Check whether this range has the same constraints as another range, ignoring the
+    /// versioning scheme.
+    ///
+    /// Ranges can only have the same constraints if they use the same version type.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The range to compare with
+    ///
+    /// # Returns
+    ///
+    /// `true` if both ranges use the same version type and have equal constraint lists
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let npm: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let semver: DynamicVersionRange = "vers:semver/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert_ne!(npm, semver);
+    /// assert!(npm.same_constraints(&semver));
+    /// ```
+    pub fn same_constraints(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DynamicVersionRange::SemVer(a), DynamicVersionRange::SemVer(b)) => a.same_constraints(b),
+            (DynamicVersionRange::Numeric(a), DynamicVersionRange::Numeric(b)) => a.same_constraints(b),
+            (DynamicVersionRange::Conda(a), DynamicVersionRange::Conda(b)) => a.same_constraints(b),
+            _ => false,
         }
+    }
 
-        // Get versioning scheme
-        let versioning_scheme = specifier_parts[0].to_lowercase();
-        if versioning_scheme.is_empty() {
-            return Err(VersError::MissingVersioningScheme);
+    /// Select the version strings contained within this range.
+    ///
+    /// # Arguments
+    ///
+    /// * `versions` - The version strings to filter
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the contained version strings in their input order, or an
+    /// error if a version string cannot be parsed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let range: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert_eq!(range.filter_matching(["0.9.0", "1.5.0", "2.0.0"]).unwrap(), vec!["1.5.0"]);
+    /// ```
+    pub fn filter_matching<'a>(
+        &self,
+        versions: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<&'a str>, VersError> {
+        let mut matching = Vec::new();
+        for version in versions {
+            if self.contains(version)? {
+                matching.push(version);
+            }
         }
+        Ok(matching)
+    }
 
-        Ok(versioning_scheme)
+    /// Get the canonical name of this range's versioning scheme, mapping aliases such as
+    /// "npm" and "semver" to a single name.
+    ///
+    /// # Returns
+    ///
+    /// The canonical scheme name, or the scheme as-is if it is unknown
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let range: DynamicVersionRange = "vers:npm/>=1.0.0".parse().unwrap();
+    /// assert_eq!(range.canonical_scheme(), "semver");
+    /// ```
+    pub fn canonical_scheme(&self) -> &str {
+        schemes::canonical_scheme(self.versioning_scheme()).unwrap_or(self.versioning_scheme())
+    }
+
+    /// Check whether this range equals another range, treating aliases of the same
+    /// versioning scheme (such as "npm" and "semver") as equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The range to compare with
+    ///
+    /// # Returns
+    ///
+    /// `true` if both ranges have the same canonical scheme and equal constraint lists
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let npm: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let semver: DynamicVersionRange = "vers:semver/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert_ne!(npm, semver);
+    /// assert!(npm.eq_ignoring_scheme_aliases(&semver));
+    /// ```
+    pub fn eq_ignoring_scheme_aliases(&self, other: &Self) -> bool {
+        self.canonical_scheme() == other.canonical_scheme() && self.same_constraints(other)
+    }
+
+    /// Check whether this range and another range have no version in common.
+    ///
+    /// See `GenericVersionRange::is_disjoint` for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The range to compare with
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `true` if no version is contained within both ranges, or an
+    /// error if the ranges use different versioning schemes
+    pub fn is_disjoint(&self, other: &Self) -> Result<bool, VersError> {
+        match (self, other) {
+            (DynamicVersionRange::SemVer(a), DynamicVersionRange::SemVer(b)) => a.is_disjoint(b),
+            (DynamicVersionRange::Numeric(a), DynamicVersionRange::Numeric(b)) => a.is_disjoint(b),
+            (DynamicVersionRange::Conda(a), DynamicVersionRange::Conda(b)) => a.is_disjoint(b),
+            _ => Err(self.incompatible_with(other)),
+        }
+    }
+
+    /// Get the boundary versions of this range in ascending order, as strings.
+    ///
+    /// See `GenericVersionRange::boundaries` for details.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` of the tagged boundary versions
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let range: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert_eq!(range.boundaries()[1], (Bound::Excluded("2.0.0".to_string()), "2.0.0".to_string()));
+    /// ```
+    pub fn boundaries(&self) -> Vec<(Bound<String>, String)> {
+        with_range!(self, range => range
+            .boundaries()
+            .map(|(bound, version)| (bound.map(|v| v.to_string()), version.to_string()))
+            .collect())
+    }
+
+    /// Describe this range in English prose, for human-readable reports.
+    ///
+    /// See `GenericVersionRange::describe` for details.
+    ///
+    /// # Returns
+    ///
+    /// The description of this range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let range: DynamicVersionRange = "vers:npm/>=1.0.0|!=1.5.0|<2.0.0".parse().unwrap();
+    /// assert_eq!(
+    ///     range.describe(),
+    ///     "versions 1.0.0 (inclusive) up to but not including 2.0.0, excluding 1.5.0 and pre-releases"
+    /// );
+    /// ```
+    pub fn describe(&self) -> String {
+        with_range!(self, range => range.describe())
+    }
+
+    /// Format this range as a vers string that writes the `=` comparator of equality
+    /// constraints explicitly.
+    ///
+    /// See `GenericVersionRange::to_string_explicit` for details.
+    ///
+    /// # Returns
+    ///
+    /// The vers string with explicit equality comparators
+    pub fn to_string_explicit(&self) -> String {
+        with_range!(self, range => range.to_string_explicit())
+    }
+
+    /// Build the error for an operation on two ranges with incompatible versioning schemes.
+    fn incompatible_with(&self, other: &Self) -> VersError {
+        VersError::IncompatibleVersioningSchemes(
+            self.versioning_scheme().to_string(),
+            other.versioning_scheme().to_string(),
+        )
+    }
+
+    /// Serialize this range to a JSON string.
+    ///
+    /// The range is represented as a JSON string containing its vers string.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the JSON string or a `VersError::Serialization` error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let range: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert_eq!(range.to_json().unwrap(), r#""vers:npm/>=1.0.0|<2.0.0""#);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, VersError> {
+        serde_json::to_string(self).map_err(|e| VersError::Serialization(e.to_string()))
+    }
+
+    /// Deserialize a range from a JSON string.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - A JSON string containing a vers string
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the parsed range or a `VersError::Serialization` error
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<Self, VersError> {
+        serde_json::from_str(json).map_err(|e| VersError::Serialization(e.to_string()))
+    }
+
+    /// Split this range into one range per contiguous interval.
+    ///
+    /// See `GenericVersionRange::split_intervals` for details.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec` of single-interval ranges in ascending version order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::range::dynamic::DynamicVersionRange;
+    ///
+    /// let range: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0|>=3.0.0|<4.0.0".parse().unwrap();
+    /// assert_eq!(range.split_intervals().len(), 2);
+    /// ```
+    pub fn split_intervals(&self) -> Vec<Self> {
+        match self {
+            DynamicVersionRange::SemVer(range) => range
+                .split_intervals()
+                .into_iter()
+                .map(DynamicVersionRange::SemVer)
+                .collect(),
+            DynamicVersionRange::Numeric(range) => range
+                .split_intervals()
+                .into_iter()
+                .map(DynamicVersionRange::Numeric)
+                .collect(),
+            DynamicVersionRange::Conda(range) => range
+                .split_intervals()
+                .into_iter()
+                .map(DynamicVersionRange::Conda)
+                .collect(),
+        }
     }
 }
 
@@ -89,9 +512,7 @@ impl VersionRange<&str> for DynamicVersionRange {
     /// assert_eq!(range.versioning_scheme(), "npm");
     /// ```
     fn versioning_scheme(&self) -> &str {
-        match self {
-            DynamicVersionRange::SemVer(range) => &range.versioning_scheme,
-        }
+        with_range!(self, range => &range.versioning_scheme)
     }
 
     /// Check if a version string is contained within this range.
@@ -118,19 +539,18 @@ impl VersionRange<&str> for DynamicVersionRange {
     /// assert!(!range.contains("2.0.0").unwrap());
     /// ```
     fn contains(&self, version_str: &str) -> Result<bool, VersError> {
-        match self {
-            DynamicVersionRange::SemVer(range) => {
-                let version: SemVer = version_str.parse()?;
-                range.contains(&version)
-            }
-        }
+        with_range!(self, range => range.contains(&VT::parse_in_scheme(version_str, range.versioning_scheme())?))
     }
 
     /// Get the constraints in this range.
     ///
+    /// As the version type depends on the versioning scheme, the versions are
+    /// returned as strings, collected anew on every call. The typed constraints are
+    /// available without copying through the variants, e.g. `DynamicVersionRange::SemVer`.
+    ///
     /// # Returns
     ///
-    /// A reference to the constraints Vec in this range
+    /// The constraints in this range, with string versions
     ///
     /// # Examples
     ///
@@ -141,10 +561,11 @@ impl VersionRange<&str> for DynamicVersionRange {
     /// let range: DynamicVersionRange = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
     /// assert_eq!(range.constraints().len(), 2);
     /// ```
-    fn constraints(&self) -> &Vec<VersionConstraint<impl VT>> {
-        match self {
-            DynamicVersionRange::SemVer(range) => &range.constraints,
-        }
+    fn constraints(&self) -> Cow<'_, [VersionConstraint<impl VT>]> {
+        with_range!(self, range => range.constraints
+            .iter()
+            .map(|c| VersionConstraint::new(c.comparator, c.version.to_string()))
+            .collect())
     }
 }
 
@@ -174,23 +595,65 @@ impl FromStr for DynamicVersionRange {
     /// assert_eq!(range.versioning_scheme(), "npm");
     /// ```
     fn from_str(s: &str) -> Result<Self, VersError> {
-        // Extract the versioning scheme first to determine which type to use
-        let versioning_scheme = Self::extract_versioning_scheme(s)?;
+        Self::from_str_with_options(s, &ParseOptions::default())
+    }
+}
 
-        match versioning_scheme.as_str() {
-            "semver" | "npm" => {
-                let range: GenericVersionRange<SemVer> = s.parse()?;
-                Ok(DynamicVersionRange::SemVer(range))
-            }
-            _ => Err(VersError::UnsupportedVersioningScheme(versioning_scheme)),
+impl From<GenericVersionRange<SemVer>> for DynamicVersionRange {
+    fn from(range: GenericVersionRange<SemVer>) -> Self {
+        DynamicVersionRange::SemVer(range)
+    }
+}
+
+/// Unwraps a SemVer range, failing with `VersError::IncompatibleVersioningSchemes` for
+/// ranges of any other version type.
+impl TryFrom<DynamicVersionRange> for GenericVersionRange<SemVer> {
+    type Error = VersError;
+
+    fn try_from(range: DynamicVersionRange) -> Result<Self, Self::Error> {
+        match range {
+            DynamicVersionRange::SemVer(range) => Ok(range),
+            other => Err(VersError::IncompatibleVersioningSchemes(
+                other.versioning_scheme().to_string(),
+                SEMVER_SCHEME.to_string(),
+            )),
         }
     }
 }
 
+/// Compares a version range against a vers string, by parsing the string into its
+/// normalized form. A string that fails to parse is never equal to a range.
+impl PartialEq<str> for DynamicVersionRange {
+    fn eq(&self, other: &str) -> bool {
+        with_range!(self, range => range == other)
+    }
+}
+
+impl PartialEq<&str> for DynamicVersionRange {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
 impl Display for DynamicVersionRange {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            DynamicVersionRange::SemVer(range) => write!(f, "{}", range),
-        }
+        with_range!(self, range => Display::fmt(range, f))
+    }
+}
+
+/// Serializes a version range as its vers string.
+#[cfg(feature = "serde")]
+impl serde::Serialize for DynamicVersionRange {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
     }
-}
\ No newline at end of file
+}
+
+/// Deserializes a version range from its vers string, detecting the versioning scheme.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DynamicVersionRange {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
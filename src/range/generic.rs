@@ -24,12 +24,18 @@
 use crate::comparator::Comparator::*;
 use crate::constraint::VT;
 use crate::error::VersError;
-use crate::VersionConstraint;
+use crate::{Comparator, ParseOptions, VersionConstraint};
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS, NON_ALPHANUMERIC};
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::LinkedList;
 use std::fmt;
 use std::fmt::Display;
+use std::ops::{BitAnd, BitOr, Bound};
 use std::str::FromStr;
-use crate::range::VersionRange;
+use crate::range::{split_specifier, CanonicalViolation, ExclusionReason, RangeRelation, VersionRange};
+use crate::range::interval;
+use crate::range::prepared::PreparedRange;
 
 /// A version range specifier.
 ///
@@ -42,13 +48,16 @@ use crate::range::VersionRange;
 /// - `vers:npm/1.2.3` (a single version)
 /// - `vers:npm/>=1.0.0|<2.0.0` (a range of versions)
 /// - `vers:pypi/*` (any version)
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct GenericVersionRange<V : VT> {
     /// The versioning scheme (e.g., "npm", "pypi", "maven", "deb")
     pub versioning_scheme: String,
     
     /// The list of version constraints
     pub constraints: Vec<VersionConstraint<V>>,
+
+    /// Whether pre-release versions are matched like any other version
+    allow_prereleases: bool,
 }
 
 impl<V: VT> VersionRange<&V> for GenericVersionRange<V> {
@@ -56,140 +65,1272 @@ impl<V: VT> VersionRange<&V> for GenericVersionRange<V> {
     ///
     /// # Returns
     ///
-    /// The versioning scheme string (e.g., "npm", "semver")
-    fn versioning_scheme(&self) -> &str {
-        self.versioning_scheme.as_str()
+    /// The versioning scheme string (e.g., "npm", "semver")
+    fn versioning_scheme(&self) -> &str {
+        self.versioning_scheme.as_str()
+    }
+
+    /// Check if a version is contained within this range.
+    ///
+    /// This method implements the algorithm described in the specification to check
+    /// if a version is contained within the range. A version is contained within a
+    /// range if it satisfies any of the constraints.
+    ///
+    /// Unless pre-releases are allowed (see `allow_prereleases`), a pre-release version is
+    /// only in the range if a constraint version allows it, following npm semantics.
+    ///
+    /// The algorithm:
+    /// 1. If the constraint list contains only "*", then the version is in the range
+    /// 2. Binary search the sorted constraints for the first constraint at or above the version
+    /// 3. If that constraint has the same version, its comparator decides
+    /// 4. Otherwise the nearest range constraints (>, >=, <, <=) around the version decide
+    ///    whether it falls within an interval
+    ///
+    /// The range is expected to be normalized, making this O(log n) for typical ranges. Its
+    /// comparators are checked against the ordering of its version type when it is
+    /// validated, not on every call, so a range created with `new` should be validated with
+    /// `normalize_and_validate` first.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version string to check
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a boolean indicating whether the version is in the range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::{parse, GenericVersionRange};
+    /// use vers_rs::range::VersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range = "vers:npm/>=1.0.0|<2.0.0".parse::<GenericVersionRange<SemVer>>().unwrap();
+    /// assert!(range.contains(&"1.5.0".parse().unwrap()).unwrap());
+    /// assert!(!range.contains(&"2.0.0".parse().unwrap()).unwrap());
+    /// ```
+    fn contains(&self, version: &V) -> Result<bool, VersError> {
+        // An empty range (e.g. the result of a set operation) contains no version
+        if self.constraints.is_empty() {
+            return Ok(false);
+        }
+
+        // Unless allowed, a pre-release is only in the range if a constraint allows it
+        if self.rejects_prerelease(version) {
+            return Ok(false);
+        }
+
+        // If the constraint list contains only "*", then the version is in the range
+        if self.is_any() {
+            return Ok(true);
+        }
+
+        // An incomparable version lies in no interval, so only an exact match or a list of
+        // exclusions contains it
+        if !version.is_comparable() {
+            return Ok(match self.constraints.iter().find(|c| c.version == *version) {
+                Some(constraint) => constraint.comparator.is_inclusive(),
+                None => self.constraints.iter().all(|c| c.comparator == NotEqual),
+            });
+        }
+
+        // The constraints of a normalized range are sorted by version, so binary search for
+        // the first constraint at or above the tested version
+        let index = self.constraints.partition_point(|c| c.version < *version);
+
+        // Check for an exact match with the constraint version. A normalized range has a
+        // single constraint per version, but if a "!=" shares its version with an inclusive
+        // bound, the exclusion takes precedence.
+        let mut matching = self.constraints[index..].iter().take_while(|c| c.version == *version).peekable();
+        if matching.peek().is_some() {
+            let mut inclusive = true;
+            for constraint in matching {
+                if constraint.comparator == NotEqual {
+                    return Ok(false);
+                }
+                inclusive &= constraint.comparator.is_inclusive();
+            }
+            return Ok(inclusive);
+        }
+
+        // Otherwise the version lies strictly between two constraint versions, and is in the
+        // range if it lies within an interval. In a normalized range, "<" and "<=" end an
+        // interval and ">" and ">=" start one, while "=" and "!=" do not affect intervals.
+        let is_bound = |c: &&VersionConstraint<V>| c.comparator.is_lower_bound() || c.comparator.is_upper_bound();
+        if let Some(next) = self.constraints[index..].iter().find(is_bound) {
+            return Ok(next.comparator.is_upper_bound());
+        }
+        match self.constraints[..index].iter().rev().find(is_bound) {
+            Some(previous) => Ok(previous.comparator.is_lower_bound()),
+            // Without any interval, only a list of exclusions contains other versions
+            None => Ok(self.constraints.iter().all(|c| c.comparator == NotEqual)),
+        }
+    }
+
+    fn constraints(&self) -> Cow<'_, [VersionConstraint<impl VT>]> {
+        Cow::Borrowed(&self.constraints)
+    }
+}
+
+impl<V : VT> GenericVersionRange<V> {
+    /// Create a new version range with the given versioning scheme and constraints.
+    ///
+    /// # Arguments
+    ///
+    /// * `versioning_scheme` - The versioning scheme to use (e.g., "npm", "pypi", "maven", "deb")
+    /// * `constraints` - The list of version constraints
+    ///
+    /// # Returns
+    ///
+    /// A new `VersionRange` instance, which is neither normalized nor validated
+    pub fn new(versioning_scheme: String, constraints: Vec<VersionConstraint<V>>) -> Self {
+        Self { versioning_scheme, constraints, allow_prereleases: false }
+    }
+
+    /// Create a new version range from constraints, normalizing and validating them.
+    ///
+    /// Unlike `new`, which takes the constraints as-is, this rejects constraint lists that
+    /// do not form a valid range, as parsing a vers string does.
+    ///
+    /// # Arguments
+    ///
+    /// * `versioning_scheme` - The versioning scheme to use (e.g., "npm", "pypi", "maven", "deb")
+    /// * `constraints` - The list of version constraints, in any order
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the normalized range or an error if the constraints are
+    /// invalid (see `normalize_and_validate`)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::{GenericVersionRange, VersionConstraint};
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let constraints = vec![
+    ///     VersionConstraint::parse("<2.0.0").unwrap(),
+    ///     VersionConstraint::parse(">=1.0.0").unwrap(),
+    /// ];
+    /// let range = GenericVersionRange::<SemVer>::try_from_parts("npm".to_string(), constraints).unwrap();
+    /// assert_eq!(range.to_string(), "vers:npm/>=1.0.0|<2.0.0");
+    /// ```
+    pub fn try_from_parts(versioning_scheme: String, constraints: Vec<VersionConstraint<V>>) -> Result<Self, VersError> {
+        Self::check_scheme_supported(&versioning_scheme)?;
+        let mut range = Self::new(versioning_scheme, constraints);
+        range.normalize_and_validate()?;
+        Ok(range)
+    }
+
+    /// Create a new version range with the same versioning scheme and settings as this range.
+    fn with_constraints(&self, constraints: Vec<VersionConstraint<V>>) -> Self {
+        Self {
+            versioning_scheme: self.versioning_scheme.clone(),
+            constraints,
+            allow_prereleases: self.allow_prereleases,
+        }
+    }
+
+    /// Set whether pre-release versions are matched like any other version.
+    ///
+    /// By default, following npm semantics, a pre-release version is only contained within
+    /// a range if a constraint version with the same release tuple is a pre-release as
+    /// well, e.g. `1.5.0-beta` is not contained in `>=1.0.0|<2.0.0`, but `1.0.0-rc.2` is
+    /// contained in `>=1.0.0-rc.1|<2.0.0`. Allowing pre-releases lifts this restriction.
+    ///
+    /// # Arguments
+    ///
+    /// * `allow` - Whether to match pre-release versions like any other version
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::range::VersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let mut range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let beta: SemVer = "1.5.0-beta".parse().unwrap();
+    /// assert!(!range.contains(&beta).unwrap());
+    ///
+    /// range.allow_prereleases(true);
+    /// assert!(range.contains(&beta).unwrap());
+    /// ```
+    pub fn allow_prereleases(&mut self, allow: bool) {
+        self.allow_prereleases = allow;
+    }
+
+    /// Check whether pre-release versions are matched like any other version.
+    ///
+    /// # Returns
+    ///
+    /// `true` if pre-releases are allowed (see `allow_prereleases`)
+    pub fn allows_prereleases(&self) -> bool {
+        self.allow_prereleases
+    }
+
+    /// Get the reason why a version is not contained within this range.
+    ///
+    /// This is a lightweight alternative to a full explanation of `contains`, suitable for
+    /// user-facing messages. The range is expected to be normalized. Every version is
+    /// reported as `InGap` for an empty range. Like `contains`, this reports pre-releases
+    /// the range does not admit as `PrereleaseNotAllowed`, whatever their position.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version to check
+    ///
+    /// # Returns
+    ///
+    /// The `ExclusionReason` for the version, or `None` if the version is contained within
+    /// this range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::range::ExclusionReason;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert_eq!(range.why_not(&"2.0.0".parse().unwrap()), Some(ExclusionReason::AboveUpperBound));
+    /// assert_eq!(range.why_not(&"1.5.0".parse().unwrap()), None);
+    /// ```
+    pub fn why_not(&self, version: &V) -> Option<ExclusionReason> {
+        if !self.constraints.is_empty() && self.rejects_prerelease(version) {
+            return Some(ExclusionReason::PrereleaseNotAllowed);
+        }
+
+        let bounds = interval::bounds(&self.constraints);
+        if !bounds.iter().any(|i| interval::interval_contains(i, version)) {
+            return match (bounds.first(), bounds.last()) {
+                (Some(first), _) if interval::is_below(first, version) => {
+                    Some(ExclusionReason::BelowLowerBound)
+                }
+                (_, Some(last)) if interval::is_above(last, version) => {
+                    Some(ExclusionReason::AboveUpperBound)
+                }
+                _ => Some(ExclusionReason::InGap),
+            };
+        }
+
+        let excluded = self.constraints
+            .iter()
+            .any(|c| c.comparator == NotEqual && &c.version == version);
+        if excluded {
+            Some(ExclusionReason::ExplicitlyExcluded)
+        } else {
+            None
+        }
+    }
+
+    /// Get the constraint nearest to a version, i.e. the boundary deciding whether the
+    /// version is just in or just out of this range.
+    ///
+    /// This is the first constraint at or above the version, or the last constraint if all
+    /// of them lie below it. The range is expected to be normalized.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version to query
+    ///
+    /// # Returns
+    ///
+    /// The comparator and version of the nearest constraint, or `None` if the range is empty
+    /// or `*`, or if the version is a pre-release the range does not admit (see
+    /// `allow_prereleases`), as no boundary decides its exclusion
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::{Comparator, GenericVersionRange};
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let (comparator, version) = range.nearest_boundary(&"1.9.9".parse().unwrap()).unwrap();
+    /// assert_eq!(*comparator, Comparator::LessThan);
+    /// assert_eq!(version.to_string(), "2.0.0");
+    /// ```
+    pub fn nearest_boundary(&self, version: &V) -> Option<(&Comparator, &V)> {
+        if self.is_any() || self.rejects_prerelease(version) {
+            return None;
+        }
+        let index = self.constraints.partition_point(|c| c.version < *version);
+        self.constraints
+            .get(index)
+            .or_else(|| self.constraints.last())
+            .map(|c| (&c.comparator, &c.version))
+    }
+
+    /// Check whether this range has the same constraints as another range, ignoring the
+    /// versioning scheme.
+    ///
+    /// This is useful to compare ranges of aliased schemes, such as "npm" and "semver".
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The range to compare with
+    ///
+    /// # Returns
+    ///
+    /// `true` if both ranges have equal constraint lists
+    pub fn same_constraints(&self, other: &Self) -> bool {
+        self.constraints == other.constraints
+    }
+
+    /// Iterate over the boundary versions of this range in ascending order.
+    ///
+    /// Each constraint version is tagged with its bound type: `Included` for the inclusive
+    /// comparators `=`, `>=` and `<=`, and `Excluded` for the exclusive comparators `>`, `<`
+    /// and `!=`. A `*` constraint has no boundary. The range is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over the tagged boundary versions
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let mut boundaries = range.boundaries();
+    /// assert!(matches!(boundaries.next(), Some((Bound::Included(_), _))));
+    /// assert!(matches!(boundaries.next(), Some((Bound::Excluded(_), _))));
+    /// ```
+    pub fn boundaries(&self) -> impl Iterator<Item = (Bound<&V>, &V)> {
+        self.constraints.iter().filter_map(|c| match c.comparator {
+            Equal | GreaterThanOrEqual | LessThanOrEqual => Some((Bound::Included(&c.version), &c.version)),
+            GreaterThan | LessThan | NotEqual => Some((Bound::Excluded(&c.version), &c.version)),
+            Any => None,
+        })
+    }
+
+    /// Get the canonical string form of this range, suitable as a map key.
+    ///
+    /// The canonical form is computed from the set of versions covered by the range, so
+    /// equivalent ranges yield the same string regardless of their input formatting, e.g.
+    /// whitespace, constraint order, percent-encoding or `!=` exclusions outside of the
+    /// range. The range is expected to be normalized.
+    ///
+    /// # Stability
+    ///
+    /// Unlike `Display`, whose output may evolve, the canonical form is guaranteed to be
+    /// byte-stable across crate versions for the same logical range: `vers:`, the lowercase
+    /// versioning scheme, `/` and the constraints sorted by version and separated by `|`.
+    /// `=` comparators are elided and only `%`, `|` and control characters in versions are
+    /// percent-encoded. An empty range has nothing after the `/`.
+    ///
+    /// # Returns
+    ///
+    /// The canonical string form of this range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let a: GenericVersionRange<SemVer> = "vers:npm/ <2.0.0 | >=1.0.0 ".parse().unwrap();
+    /// let b: GenericVersionRange<SemVer> = "vers:NPM/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert_eq!(a.canonical_string(), b.canonical_string());
+    /// ```
+    pub fn canonical_string(&self) -> String {
+        let intervals = interval::from_constraints(&self.constraints);
+        let mut canonical = format!("vers:{}/", self.versioning_scheme);
+        for (i, constraint) in interval::to_constraints(&intervals).iter().enumerate() {
+            if i > 0 {
+                canonical.push('|');
+            }
+            let version = constraint.version.to_string();
+            let version = utf8_percent_encode(&version, MINIMAL_ENCODE_SET);
+            match constraint.comparator {
+                Any => canonical.push('*'),
+                Equal => canonical.push_str(&version.to_string()),
+                _ => canonical.push_str(&format!("{}{}", constraint.comparator, version)),
+            }
+        }
+        canonical
+    }
+
+    /// Get the equivalent range with the fewest constraints.
+    ///
+    /// The constraints are rebuilt from the intervals of versions covered by this range, so
+    /// `!=` exclusions outside of any interval and `=` versions inside an interval are
+    /// dropped, and two intervals separated by a single version are joined by a `!=`
+    /// exclusion. The range is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// The minimal range, covering the same versions as this range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0|!=3.0.0".parse().unwrap();
+    /// assert_eq!(range.minimal_form().to_string(), "vers:npm/>=1.0.0|<2.0.0");
+    /// ```
+    pub fn minimal_form(&self) -> Self {
+        self.with_constraints(interval::to_constraints(&interval::from_constraints(&self.constraints)))
+    }
+
+    /// Check whether this range covers exactly the same versions as another range.
+    ///
+    /// Unlike `==`, this ignores how the versions are expressed, e.g. redundant `!=`
+    /// exclusions. Both ranges are expected to be normalized.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The range to compare with
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `true` if both ranges cover the same versions, or an error if
+    /// the ranges use different versioning schemes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let a: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0|!=3.0.0".parse().unwrap();
+    /// let b: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert!(a.is_equivalent(&b).unwrap());
+    /// ```
+    pub fn is_equivalent(&self, other: &Self) -> Result<bool, VersError> {
+        self.check_same_scheme(other)?;
+        Ok(interval::from_constraints(&self.constraints) == interval::from_constraints(&other.constraints))
+    }
+
+    /// Check whether this range contains a finite number of versions.
+    ///
+    /// This is only the case when the range consists solely of `=` constraints.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every constraint of this range is an `=` constraint
+    pub fn is_finite(&self) -> bool {
+        self.constraints.iter().all(|c| c.comparator == Equal)
+    }
+
+    /// Count the exact versions contained within this range.
+    ///
+    /// # Returns
+    ///
+    /// The number of `=` constraints if the range is finite (see `is_finite`), or 0 for
+    /// open-ended ranges whose versions cannot be enumerated
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/1.0.0|2.0.0".parse().unwrap();
+    /// assert_eq!(range.exact_match_count(), 2);
+    /// ```
+    pub fn exact_match_count(&self) -> usize {
+        if self.is_finite() {
+            self.constraints.len()
+        } else {
+            0
+        }
+    }
+
+    /// Check whether this range contains no version at all.
+    ///
+    /// Set operations such as `difference` may produce an empty range, which has no
+    /// constraints. Bounds excluding the version they share, such as `>1.0.0|<1.0.0`, are
+    /// empty as well, but rejected when normalizing. Unless pre-releases are allowed (see `allow_prereleases`), a range
+    /// whose intervals only hold pre-releases, such as `>1.0.0|<1.0.1`, is empty as well.
+    /// As constraint versions that are pre-releases admit pre-releases into the range,
+    /// such ranges are only empty if they contain no version at all.
+    ///
+    /// # Returns
+    ///
+    /// `true` if no version is contained within this range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let mut range: GenericVersionRange<SemVer> = "vers:npm/>1.0.0|<1.0.1".parse().unwrap();
+    /// assert!(range.is_empty());
+    /// range.allow_prereleases(true);
+    /// assert!(!range.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        let intervals = interval::from_constraints(&self.constraints);
+        if self.allow_prereleases || self.constraints.iter().any(|c| c.version.is_prerelease()) {
+            return intervals.is_empty();
+        }
+        !intervals.iter().any(interval::contains_release)
+    }
+
+    /// Compute the versions contained within this range but not within another range.
+    ///
+    /// Both ranges are expected to be normalized. The resulting range is normalized and
+    /// may be empty (see `is_empty`).
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The range whose versions are removed from this range
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the difference range or an error if the ranges use
+    /// different versioning schemes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let a: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<3.0.0".parse().unwrap();
+    /// let b: GenericVersionRange<SemVer> = "vers:npm/>=2.0.0".parse().unwrap();
+    /// assert_eq!(a.difference(&b).unwrap().to_string(), "vers:npm/>=1.0.0|<2.0.0");
+    /// ```
+    pub fn difference(&self, other: &Self) -> Result<Self, VersError> {
+        self.check_same_scheme(other)?;
+        let remaining = interval::intersect(
+            &interval::from_constraints(&self.constraints),
+            &interval::complement(&interval::from_constraints(&other.constraints)),
+        );
+        Ok(self.with_constraints(interval::to_constraints(&remaining)))
+    }
+
+    /// Compute the versions contained within this range or within another range.
+    ///
+    /// Both ranges are expected to be normalized. If either range is `*`, the result is `*`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The range to combine with this range
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the normalized union range or an error if the ranges
+    /// use different versioning schemes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let a: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let b: GenericVersionRange<SemVer> = "vers:npm/>=2.0.0|<3.0.0".parse().unwrap();
+    /// assert_eq!(a.union(&b).unwrap().to_string(), "vers:npm/>=1.0.0|<3.0.0");
+    /// ```
+    pub fn union(&self, other: &Self) -> Result<Self, VersError> {
+        self.check_same_scheme(other)?;
+        // "*" has no comparable version, so it is resolved before the interval math
+        if self.is_any() {
+            return Ok(self.clone());
+        }
+        if other.is_any() {
+            return Ok(other.clone());
+        }
+        let mut combined = interval::from_constraints(&self.constraints);
+        combined.extend(interval::from_constraints(&other.constraints));
+        Ok(self.with_constraints(interval::to_constraints(&interval::normalize(combined))))
+    }
+
+    /// Compute the versions contained within both this range and another range.
+    ///
+    /// Both ranges are expected to be normalized. If either range is `*`, the result is the
+    /// other range. The result may be empty (see `is_empty`).
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The range to intersect with this range
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the normalized intersection range or an error if the
+    /// ranges use different versioning schemes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let a: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<3.0.0".parse().unwrap();
+    /// let b: GenericVersionRange<SemVer> = "vers:npm/>=2.0.0".parse().unwrap();
+    /// assert_eq!(a.intersect(&b).unwrap().to_string(), "vers:npm/>=2.0.0|<3.0.0");
+    /// ```
+    pub fn intersect(&self, other: &Self) -> Result<Self, VersError> {
+        self.check_same_scheme(other)?;
+        // "*" has no comparable version, so it is resolved before the interval math
+        if self.is_any() {
+            return Ok(other.clone());
+        }
+        if other.is_any() {
+            return Ok(self.clone());
+        }
+        let common = interval::intersect(
+            &interval::from_constraints(&self.constraints),
+            &interval::from_constraints(&other.constraints),
+        );
+        Ok(self.with_constraints(interval::to_constraints(&common)))
+    }
+
+    /// Check if an owned version is contained within this range.
+    ///
+    /// This is `contains` for versions produced inline, without binding them first.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version to check
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a boolean indicating whether the version is in the range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert!(range.contains_owned("1.5.0".parse().unwrap()).unwrap());
+    /// ```
+    pub fn contains_owned(&self, version: V) -> Result<bool, VersError> {
+        self.contains(&version)
+    }
+
+    /// Turn this range into a prepared range, which is cheap to clone and can be shared
+    /// across threads.
+    ///
+    /// The range is checked once for comparators its versions support, as `contains` relies
+    /// on them without checking. Clone the range first to keep it.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the prepared range, or `VersError::OrderingUnsupported`
+    /// or `VersError::InvalidConstraint` if the range uses ordered comparators on versions
+    /// without an ordering
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::range::VersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let prepared = range.into_prepared().unwrap();
+    /// assert!(prepared.contains(&"1.5.0".parse().unwrap()).unwrap());
+    /// ```
+    pub fn into_prepared(self) -> Result<PreparedRange<V>, VersError> {
+        self.check_ordering()?;
+        Ok(PreparedRange::new(self))
+    }
+
+    /// Select the versions contained within this range.
+    ///
+    /// # Arguments
+    ///
+    /// * `versions` - The versions to filter
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the contained versions in their input order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let versions: Vec<SemVer> = ["0.9.0", "1.5.0"].iter().map(|v| v.parse().unwrap()).collect();
+    /// assert_eq!(range.filter_matching(versions).unwrap().len(), 1);
+    /// ```
+    pub fn filter_matching(&self, versions: impl IntoIterator<Item = V>) -> Result<Vec<V>, VersError> {
+        let mut matching = Vec::new();
+        for version in versions {
+            if self.contains(&version)? {
+                matching.push(version);
+            }
+        }
+        Ok(matching)
+    }
+
+    /// Replace this range by the explicit enumeration of the known versions it contains.
+    ///
+    /// # Arguments
+    ///
+    /// * `known` - The known versions, e.g. all published versions of a package
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a range of `=` constraints for the known versions within this
+    /// range, `*` if it contains all known versions, or an empty range if it contains none
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let known: Vec<SemVer> = ["0.9.0", "1.0.0", "1.5.0", "2.0.0"].iter().map(|v| v.parse().unwrap()).collect();
+    /// assert_eq!(range.materialize(&known).unwrap().to_string(), "vers:npm/1.0.0|1.5.0");
+    /// ```
+    pub fn materialize(&self, known: &[V]) -> Result<Self, VersError> {
+        let mut matching = self.filter_matching(known.iter().cloned())?;
+        if !known.is_empty() && matching.len() == known.len() {
+            return Ok(self.with_constraints(vec![VersionConstraint::new(Any, V::default())]));
+        }
+        matching.sort();
+        matching.dedup();
+        Ok(self.with_constraints(
+            matching.into_iter().map(|v| VersionConstraint::new(Equal, v)).collect(),
+        ))
+    }
+
+    /// Check whether every version of an interval is contained within this range.
+    ///
+    /// The interval must lie within a single interval of this range, without any `!=`
+    /// exclusion inside it. Like `contains`, an interval bounded by a pre-release the range
+    /// does not admit (see `allow_prereleases`) is not contained, while pre-releases strictly
+    /// inside the interval are not considered. The range is expected to be normalized.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The lower bound of the interval
+    /// * `upper` - The upper bound of the interval
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `true` if the interval is a subset of this range, or
+    /// `VersError::InvalidRange` if the interval contains no version at all
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// let (a, b): (SemVer, SemVer) = ("1.0.0".parse().unwrap(), "2.0.0".parse().unwrap());
+    /// assert!(range.contains_interval(Bound::Included(&a), Bound::Excluded(&b)).unwrap());
+    /// assert!(!range.contains_interval(Bound::Included(&a), Bound::Included(&b)).unwrap());
+    /// ```
+    pub fn contains_interval(&self, lower: Bound<&V>, upper: Bound<&V>) -> Result<bool, VersError> {
+        let (lower, upper) = (lower.cloned(), upper.cloned());
+        if interval::is_empty_interval(&lower, &upper) {
+            return Err(VersError::InvalidRange(format!(
+                "Empty interval ({:?}, {:?})",
+                lower, upper
+            )));
+        }
+        let rejected = |bound: &Bound<V>| {
+            matches!(bound, Bound::Included(v) | Bound::Excluded(v) if self.rejects_prerelease(v))
+        };
+        if rejected(&lower) || rejected(&upper) {
+            return Ok(false);
+        }
+        Ok(interval::covers(&interval::from_constraints(&self.constraints), &(lower, upper)))
+    }
+
+    /// Check whether this range and another range have no version in common.
+    ///
+    /// Both ranges are expected to be normalized.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The range to compare with
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing `true` if no version is contained within both ranges, or an
+    /// error if the ranges use different versioning schemes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let a: GenericVersionRange<SemVer> = "vers:npm/<2.0.0".parse().unwrap();
+    /// let b: GenericVersionRange<SemVer> = "vers:npm/>=2.0.0".parse().unwrap();
+    /// assert!(a.is_disjoint(&b).unwrap());
+    /// ```
+    pub fn is_disjoint(&self, other: &Self) -> Result<bool, VersError> {
+        self.check_same_scheme(other)?;
+        Ok(!interval::intersects(
+            &interval::from_constraints(&self.constraints),
+            &interval::from_constraints(&other.constraints),
+        ))
+    }
+
+    /// Classify how the versions covered by this range relate to those of another range.
+    ///
+    /// Both ranges are expected to be normalized.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The range to compare with
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the relation of this range to the other range, or an error if
+    /// the ranges use different versioning schemes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::range::RangeRelation;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let a: GenericVersionRange<SemVer> = "vers:npm/>=1.2.0|<1.5.0".parse().unwrap();
+    /// let b: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert_eq!(a.relation_to(&b).unwrap(), RangeRelation::Subset);
+    /// ```
+    pub fn relation_to(&self, other: &Self) -> Result<RangeRelation, VersError> {
+        self.check_same_scheme(other)?;
+        let own = interval::from_constraints(&self.constraints);
+        let others = interval::from_constraints(&other.constraints);
+        if own == others {
+            return Ok(RangeRelation::Equal);
+        }
+        let common = interval::intersect(&own, &others);
+        Ok(if common.is_empty() {
+            RangeRelation::Disjoint
+        } else if common == own {
+            RangeRelation::Subset
+        } else if common == others {
+            RangeRelation::Superset
+        } else {
+            RangeRelation::Overlapping
+        })
+    }
+
+    /// Compute the versions newly covered by this range compared to a previous range.
+    ///
+    /// This frames `difference` for advisory diffing: if an advisory's affected range grows
+    /// from `previous` to `self`, the result contains exactly the newly affected versions.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - The previous version of this range
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the (possibly empty) range of newly covered versions or
+    /// an error if the ranges use different versioning schemes
+    pub fn newly_covered(&self, previous: &Self) -> Result<Self, VersError> {
+        self.difference(previous)
+    }
+
+    /// Check whether a version is covered by this range but was not covered by a previous range.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - The previous version of this range
+    /// * `version` - The version to check
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing a boolean indicating whether the version is newly covered, or
+    /// an error if the ranges use different versioning schemes
+    pub fn covers_newly(&self, previous: &Self, version: &V) -> Result<bool, VersError> {
+        self.check_same_scheme(previous)?;
+        Ok(self.contains(version)? && !previous.contains(version)?)
+    }
+
+    /// Get the intervals of this range, ignoring `!=` exclusions.
+    ///
+    /// `=` constraints yield single-version intervals. Together with `exclusions`, this
+    /// fully describes the range. The range is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// The disjoint intervals of this range, as lower and upper bounds in ascending order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|!=1.5.0|<2.0.0".parse().unwrap();
+    /// let intervals = range.to_intervals();
+    /// assert_eq!(intervals.len(), 1);
+    /// assert_eq!(intervals[0].1, Bound::Excluded("2.0.0".parse().unwrap()));
+    /// ```
+    pub fn to_intervals(&self) -> Vec<(Bound<V>, Bound<V>)> {
+        interval::bounds(&self.constraints)
+    }
+
+    /// Get the intervals of this range with every `!=` exclusion resolved.
+    ///
+    /// Unlike `to_intervals`, an exclusion inside an interval splits it into two intervals,
+    /// so the result fully describes the range on its own. The range is expected to be
+    /// normalized.
+    ///
+    /// # Returns
+    ///
+    /// The disjoint intervals of this range, as lower and upper bounds in ascending order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|!=1.5.0|<2.0.0".parse().unwrap();
+    /// let intervals = range.to_disjoint_normal_form();
+    /// assert_eq!(intervals.len(), 2);
+    /// assert_eq!(intervals[0].1, Bound::Excluded("1.5.0".parse().unwrap()));
+    /// ```
+    pub fn to_disjoint_normal_form(&self) -> Vec<(Bound<V>, Bound<V>)> {
+        interval::from_constraints(&self.constraints)
+    }
+
+    /// Get the gaps between the intervals of this range, i.e. the versions not contained
+    /// within this range but lying between its lowest and highest bound.
+    ///
+    /// Each `!=` exclusion inside an interval is a gap of the single excluded version. The
+    /// range is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// The uncovered intervals in ascending order, as lower and upper bounds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<1.5.0|>=2.0.0|<3.0.0".parse().unwrap();
+    /// assert_eq!(range.internal_gaps(), vec![
+    ///     (Bound::Included("1.5.0".parse().unwrap()), Bound::Excluded("2.0.0".parse().unwrap())),
+    /// ]);
+    /// ```
+    pub fn internal_gaps(&self) -> Vec<(Bound<V>, Bound<V>)> {
+        // Gaps extending to an unbounded end lie outside of the span of the range
+        interval::complement(&interval::from_constraints(&self.constraints))
+            .into_iter()
+            .filter(|(lower, upper)| *lower != Bound::Unbounded && *upper != Bound::Unbounded)
+            .collect()
+    }
+
+    /// Count the contiguous intervals of this range, ignoring `!=` exclusions.
+    ///
+    /// Each `=` constraint outside other intervals counts as an interval of its own. The
+    /// range is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// The number of intervals, which is zero for an empty range
+    pub fn interval_count(&self) -> usize {
+        self.to_intervals().len()
+    }
+
+    /// Check whether this range is a single contiguous interval, ignoring `!=` exclusions.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the range has exactly one interval
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|!=1.5.0|<2.0.0".parse().unwrap();
+    /// assert!(range.is_single_interval());
+    /// ```
+    pub fn is_single_interval(&self) -> bool {
+        self.interval_count() == 1
+    }
+
+    /// Get the versions excluded from this range by `!=` constraints.
+    ///
+    /// # Returns
+    ///
+    /// The excluded versions in ascending order
+    pub fn exclusions(&self) -> Vec<&V> {
+        self.constraints
+            .iter()
+            .filter(|c| c.comparator == NotEqual)
+            .map(|c| &c.version)
+            .collect()
+    }
+
+    /// Describe this range in English prose, for human-readable reports.
+    ///
+    /// For example, `>=1.0.0|!=1.5.0|<2.0.0` is described as "versions 1.0.0 (inclusive) up
+    /// to but not including 2.0.0, excluding 1.5.0". Unless pre-releases are allowed (see
+    /// `allow_prereleases`), ranges spanning more than single versions of a scheme with
+    /// pre-releases mention that those are excluded, except for the releases whose
+    /// pre-releases a constraint admits. The range is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// The description of this range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+    /// assert_eq!(
+    ///     range.describe(),
+    ///     "versions 1.0.0 (inclusive) up to but not including 2.0.0, excluding pre-releases"
+    /// );
+    /// ```
+    pub fn describe(&self) -> String {
+        let intervals: Vec<String> = self
+            .to_intervals()
+            .iter()
+            .map(|interval| match interval {
+                (Bound::Included(l), Bound::Included(u)) if l == u => format!("version {}", l),
+                (lower, upper) => {
+                    let lower = match lower {
+                        Bound::Included(l) => format!("versions {} (inclusive)", l),
+                        Bound::Excluded(l) => format!("versions above {}", l),
+                        Bound::Unbounded => "all versions".to_string(),
+                    };
+                    match upper {
+                        Bound::Included(u) => format!("{} up to and including {}", lower, u),
+                        Bound::Excluded(u) => format!("{} up to but not including {}", lower, u),
+                        Bound::Unbounded if matches!(interval.0, Bound::Included(_)) => {
+                            format!("{} and above", lower)
+                        }
+                        Bound::Unbounded => lower,
+                    }
+                }
+            })
+            .collect();
+        if intervals.is_empty() {
+            return "no versions".to_string();
+        }
+
+        let mut description = intervals.join(", or ");
+        let excluded: Vec<String> = self.exclusions().iter().map(|v| v.to_string()).collect();
+
+        // Schemes telling their next release distinguish pre-releases (see `VT::next_release`)
+        let spans_versions = self
+            .to_intervals()
+            .iter()
+            .any(|interval| !matches!(interval, (Bound::Included(l), Bound::Included(u)) if l == u));
+        let mut prereleases = None;
+        if !self.allow_prereleases && spans_versions && V::example().next_release().is_some() {
+            let mut admitted: Vec<String> = self
+                .constraints
+                .iter()
+                .filter(|c| c.version.is_prerelease())
+                .map(|c| c.version.to_release().to_string())
+                .collect();
+            admitted.dedup();
+            prereleases = Some(if admitted.is_empty() {
+                "pre-releases".to_string()
+            } else {
+                format!("pre-releases other than those of {}", admitted.join(", "))
+            });
+        }
+
+        if !excluded.is_empty() || prereleases.is_some() {
+            description.push_str(", excluding ");
+            description.push_str(&excluded.join(", "));
+            if let Some(prereleases) = prereleases {
+                if !excluded.is_empty() {
+                    description.push_str(" and ");
+                }
+                description.push_str(&prereleases);
+            }
+        }
+        description
     }
 
-    /// Check if a version is contained within this range.
+    /// Split this range into one range per contiguous interval.
     ///
-    /// This method implements the algorithm described in the specification to check
-    /// if a version is contained within the range. A version is contained within a
-    /// range if it satisfies any of the constraints.
+    /// Each resulting range covers exactly one interval of this range (or a single version
+    /// for `=` constraints), together with the `!=` exclusions that fall inside it. The range
+    /// is expected to be normalized.
     ///
-    /// The algorithm:
-    /// 1. If the constraint list contains only "*", then the version is in the range
-    /// 2. Check for exact matches with equality comparators
-    /// 3. Check for exact matches with inequality comparators
-    /// 4. Check range constraints (>, >=, <, <=) to see if the version falls within any interval
+    /// # Returns
     ///
-    /// # Arguments
+    /// A `Vec` of single-interval ranges in ascending version order
     ///
-    /// * `version` - The version string to check
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> =
+    ///     "vers:npm/>=1.0.0|<2.0.0|>=3.0.0|<4.0.0".parse().unwrap();
+    /// let parts: Vec<String> = range.split_intervals().iter().map(|r| r.to_string()).collect();
+    /// assert_eq!(parts, vec!["vers:npm/>=1.0.0|<2.0.0", "vers:npm/>=3.0.0|<4.0.0"]);
+    /// ```
+    pub fn split_intervals(&self) -> Vec<Self> {
+        let excluded = self.exclusions();
+        interval::bounds(&self.constraints)
+            .into_iter()
+            .map(|bounds| {
+                let intervals = interval::exclude(vec![bounds], &excluded);
+                self.with_constraints(interval::to_constraints(&intervals))
+            })
+            .collect()
+    }
+
+    /// Check whether this range consists of the single constraint `*`.
+    fn is_any(&self) -> bool {
+        matches!(self.constraints.as_slice(), [only] if only.comparator == Any)
+    }
+
+    /// Check whether this range is in the canonical form of the vers specification, as
+    /// produced by `normalize_and_validate`.
+    ///
+    /// Ranges parsed from a string are always canonical, but ranges created with `new` may
+    /// not be. Unlike validation, this reports every deviation instead of the first one.
     ///
     /// # Returns
     ///
-    /// A `Result` containing a boolean indicating whether the version is in the range
+    /// `Ok(())` if the range is canonical, or the list of violations grouped by rule, each
+    /// in ascending constraint order
     ///
     /// # Examples
     ///
     /// ```
-    /// use vers_rs::{parse, GenericVersionRange};
-    /// use vers_rs::range::VersionRange;
+    /// use vers_rs::{GenericVersionRange, VersionConstraint};
+    /// use vers_rs::range::CanonicalViolation;
     /// use vers_rs::schemes::semver::SemVer;
     ///
-    /// let range = "vers:npm/>=1.0.0|<2.0.0".parse::<GenericVersionRange<SemVer>>().unwrap();
-    /// assert!(range.contains(&"1.5.0".parse().unwrap()).unwrap());
-    /// assert!(!range.contains(&"2.0.0".parse().unwrap()).unwrap());
+    /// let range = GenericVersionRange::<SemVer>::new("npm".to_string(), vec![
+    ///     VersionConstraint::parse("<2.0.0").unwrap(),
+    ///     VersionConstraint::parse(">=1.0.0").unwrap(),
+    /// ]);
+    /// assert_eq!(range.check_canonical(), Err(vec![CanonicalViolation::Unsorted { index: 1 }]));
     /// ```
-    fn contains(&self, version: &V) -> Result<bool, VersError> {
-        // If the constraint list contains only "*", then the version is in the range
-        if self.constraints.len() == 1 && self.constraints[0].comparator == Any {
-            return Ok(true);
+    pub fn check_canonical(&self) -> Result<(), Vec<CanonicalViolation>> {
+        let mut violations = Vec::new();
+        if self.constraints.is_empty() {
+            violations.push(CanonicalViolation::EmptyConstraints);
+        }
+        if self.constraints.len() > 1 {
+            violations.extend(
+                self.constraints
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| c.comparator == Any)
+                    .map(|(index, _)| CanonicalViolation::StarNotAlone { index }),
+            );
         }
 
-        // Check for exact matches with equality and inequality comparators
-        for constraint in &self.constraints {
-            match constraint.comparator {
-                Equal | GreaterThanOrEqual | LessThanOrEqual => {
-                    if version == &constraint.version {
-                        return Ok(true);
-                    }
-                },
-                NotEqual => {
-                    if version == &constraint.version {
-                        return Ok(false);
-                    }
-                },
-                _ => {}
+        // Constraints must be sorted by strictly ascending version
+        for (index, pair) in self.constraints.windows(2).enumerate() {
+            if pair.iter().any(|c| c.comparator == Any) {
+                continue;
+            }
+            match pair[0].version.cmp(&pair[1].version) {
+                Ordering::Greater => violations.push(CanonicalViolation::Unsorted { index: index + 1 }),
+                Ordering::Equal => violations.push(CanonicalViolation::DuplicateVersion { index: index + 1 }),
+                Ordering::Less => {}
             }
         }
 
-        // If there are only NotEqual constraints, and we've checked them all without returning,
-        // then the version is in the range
-        if self.constraints.iter().all(|c| c.comparator == NotEqual) {
-            return Ok(true);
+        // Ignoring "!=", a "=" constraint must be followed by one of "=", ">" or ">="
+        let significant: Vec<(usize, Comparator)> = self.constraints
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !matches!(c.comparator, NotEqual | Any))
+            .map(|(i, c)| (i, c.comparator))
+            .collect();
+        for pair in significant.windows(2) {
+            if pair[0].1 == Equal && !(pair[1].1 == Equal || pair[1].1.is_lower_bound()) {
+                violations.push(CanonicalViolation::DisallowedAfterEqual { index: pair[1].0 });
+            }
         }
 
-        // Get range constraints
-        let mut range_iterator = self.constraints.iter()
-            .filter(|c| {
-                matches!(
-                    c.comparator,
-                    LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual
-                )
-            })
-            .peekable();
-
-        // Iterate over pairs of range constraints
-        let mut first = true;
-        while let Some(current) = range_iterator.next() {
-            // If this is the first iteration and the current comparator is "<" or "<="
-            // and the tested version is less than the current version
-            if first {
-                if (current.comparator == LessThan || current.comparator == LessThanOrEqual) &&
-                    version < &current.version
-                {
-                    return Ok(true);
-                }
-                first = false;
+        // Ignoring "=" and "!=", lower and upper bounds must alternate. Of two lower bounds the
+        // second one is redundant, and of two upper bounds the first one.
+        let bounds: Vec<(usize, Comparator)> = significant
+            .into_iter()
+            .filter(|(_, c)| *c != Equal)
+            .collect();
+        for pair in bounds.windows(2) {
+            if pair[0].1.is_lower_bound() && pair[1].1.is_lower_bound() {
+                violations.push(CanonicalViolation::RedundantBound { index: pair[1].0 });
+            } else if pair[0].1.is_upper_bound() && pair[1].1.is_upper_bound() {
+                violations.push(CanonicalViolation::RedundantBound { index: pair[0].0 });
             }
+        }
 
-            // If this is the last iteration and the current comparator is ">" or ">="
-            // and the tested version is greater than the current version
-            if range_iterator.peek().is_none() &&
-                (current.comparator == GreaterThan || current.comparator == GreaterThanOrEqual) &&
-                version > &current.version
-            {
-                return Ok(true);
-            }
+        if violations.is_empty() { Ok(()) } else { Err(violations) }
+    }
 
-            // If there's a next constraint
-            if let Some(next) = range_iterator.peek() {
-                // If the current comparator is ">" or ">=" and the next comparator is "<" or "<="
-                // and the tested version is greater than the current version
-                // and the tested version is less than the next version
-                if matches!(current.comparator, GreaterThan | GreaterThanOrEqual)
-                    && version > &current.version
-                    && matches!(next.comparator, LessThan | LessThanOrEqual)
-                    && version < &next.version {
-                    return Ok(true);
-                }
-            }
+    /// Ensure the version type of this range implements the given versioning scheme.
+    fn check_scheme_supported(versioning_scheme: &str) -> Result<(), VersError> {
+        if !V::CANONICAL_SCHEMES.is_empty() && !V::CANONICAL_SCHEMES.contains(&versioning_scheme) {
+            return Err(VersError::IncompatibleVersioningSchemes(
+                versioning_scheme.to_string(),
+                V::CANONICAL_SCHEMES.join("/"),
+            ));
         }
+        Ok(())
+    }
 
-        // If we get here, the version is not in the range
-        Ok(false)
+    /// Check whether a version is a pre-release this range does not admit, as neither are
+    /// pre-releases allowed nor does a constraint version allow it.
+    fn rejects_prerelease(&self, version: &V) -> bool {
+        !self.allow_prereleases
+            && version.is_prerelease()
+            && !self.constraints.iter().any(|c| c.version.allows_prerelease(version))
     }
 
-    fn constraints(&self) -> &Vec<VersionConstraint<impl VT>> {
-        &self.constraints
+    /// Ensure another range uses the same versioning scheme as this range.
+    fn check_same_scheme(&self, other: &Self) -> Result<(), VersError> {
+        if self.versioning_scheme != other.versioning_scheme {
+            return Err(VersError::IncompatibleVersioningSchemes(
+                self.versioning_scheme.clone(),
+                other.versioning_scheme.clone(),
+            ));
+        }
+        Ok(())
     }
-}
 
-impl<V : VT> GenericVersionRange<V> {
-    /// Create a new version range with the given versioning scheme and constraints.
-    ///
-    /// # Arguments
-    ///
-    /// * `versioning_scheme` - The versioning scheme to use (e.g., "npm", "pypi", "maven", "deb")
-    /// * `constraints` - The list of version constraints
-    ///
-    /// # Returns
-    ///
-    /// A new `VersionRange` instance
-    pub fn new(versioning_scheme: String, constraints: Vec<VersionConstraint<V>>) -> Self {
-        Self { versioning_scheme, constraints }
+    /// Ensure this range only uses ordered comparators if its version type has an ordering,
+    /// and only on comparable versions.
+    fn check_ordering(&self) -> Result<(), VersError> {
+        let is_ordered = |c: &&VersionConstraint<V>| c.comparator.is_lower_bound() || c.comparator.is_upper_bound();
+        let mut ordered = self.constraints.iter().filter(is_ordered).peekable();
+        if !V::HAS_ORDERING && ordered.peek().is_some() {
+            return Err(VersError::OrderingUnsupported(self.versioning_scheme.clone()));
+        }
+        if let Some(constraint) = ordered.find(|c| !c.version.is_comparable()) {
+            return Err(VersError::InvalidConstraint(format!(
+                "Version cannot be ordered: {}{}",
+                constraint.comparator, constraint.version
+            )));
+        }
+        Ok(())
     }
 
     /// Normalize and validate the version range in a single operation.
@@ -206,6 +1347,8 @@ impl<V : VT> GenericVersionRange<V> {
             return Err(VersError::EmptyConstraints);
         }
 
+        self.check_ordering()?;
+
         // Check for star constraint
         let has_star = self.constraints.iter().any(|c| c.comparator == Any);
         if has_star && self.constraints.len() > 1 {
@@ -217,23 +1360,69 @@ impl<V : VT> GenericVersionRange<V> {
             return Ok(());
         }
 
+        // Keep the constraints as given to verify that normalization preserves their meaning
+        #[cfg(debug_assertions)]
+        let original = self.constraints.clone();
+
         self.constraints.sort_by(|a, b| a.version.cmp(&b.version));
 
-        // Check for duplicate versions, exploiting sorted order
-        for i in 1..self.constraints.len() {
-            if self.constraints[i].version == self.constraints[i - 1].version {
-                return Err(VersError::DuplicateVersion(self.constraints[i].version.to_string()));
+        // Check for duplicate versions, exploiting sorted order. Only a lower and an upper
+        // bound may share a version, as in "<2.0.0|>=2.0.0", as they describe intervals
+        let mut has_shared_bounds = false;
+        for group in self.constraints.chunk_by(|a, b| a.version == b.version) {
+            match group {
+                [_] => {}
+                [a, b] if (a.comparator.is_lower_bound() && b.comparator.is_upper_bound())
+                    || (a.comparator.is_upper_bound() && b.comparator.is_lower_bound()) => {
+                    has_shared_bounds = true;
+                }
+                _ => return Err(VersError::DuplicateVersion(group[1].version.to_string())),
+            }
+        }
+
+        // Remember the pre-release constraint versions, which admit pre-releases of their
+        // release into the range, as simplification may drop their constraints
+        let prereleases: Vec<V> = self
+            .constraints
+            .iter()
+            .filter(|c| c.comparator != NotEqual && c.version.is_prerelease())
+            .map(|c| c.version.clone())
+            .collect();
+
+        // Shared bounds make the constraint list ambiguous to simplify, so it is rebuilt from
+        // the intervals it describes instead, merging those that touch. Bounds excluding
+        // their shared version, as in ">1.0.0|<1.0.0", describe no version, which a vers
+        // string cannot express, so they are rejected.
+        if has_shared_bounds {
+            let excluded: Vec<&V> = self.exclusions();
+            let intervals = interval::exclude(interval::from_unnormalized(&self.constraints), &excluded);
+            if intervals.is_empty() {
+                return Err(VersError::InvalidRange(format!("Empty range: {}", self)));
             }
+            self.constraints = interval::to_constraints(&intervals);
+        } else {
+            self.simplify()?;
         }
+        self.keep_prerelease_anchors(prereleases)?;
+
+        #[cfg(debug_assertions)]
+        self.debug_assert_same_membership(original);
+
+        Ok(())
+    }
 
+    /// Simplify a sorted constraint list without duplicate versions following the spec, then
+    /// validate the result.
+    fn simplify(&mut self) -> Result<(), VersError> {
         // First, let's perform normalization and simplification according to the README spec
 
-        // Split constraints into unequal constraints and other constraints
+        // Split constraints into unequal constraints and other constraints. Constraints on
+        // incomparable versions do not take part in intervals, so they are set aside as well.
         let mut unequal_constraints: Vec<VersionConstraint<V>> = Vec::new();
         let mut other_constraints: LinkedList<VersionConstraint<V>> = LinkedList::new();
 
         for constraint in self.constraints.drain(..) {
-            if constraint.comparator == NotEqual {
+            if constraint.comparator == NotEqual || !constraint.version.is_comparable() {
                 unequal_constraints.push(constraint);
             } else {
                 other_constraints.push_back(constraint);
@@ -254,13 +1443,8 @@ impl<V : VT> GenericVersionRange<V> {
             if let Some(next) = other_constraints.front() {
                 // If the current comparator is ">" or ">=" and next comparator is "=", ">" or ">=",
                 // discard the next constraint
-                if matches!(
-                    current.comparator,
-                    GreaterThan | GreaterThanOrEqual
-                ) && matches!(
-                    next.comparator,
-                    GreaterThan | GreaterThanOrEqual | Equal
-                ) {
+                if current.comparator.is_lower_bound()
+                    && (next.comparator.is_lower_bound() || next.comparator == Equal) {
                     // Discard the next constraint
                     other_constraints.pop_front();
                     // Re-evaluate, keeping the current constraint (re-add)
@@ -270,8 +1454,8 @@ impl<V : VT> GenericVersionRange<V> {
 
                 // If the current comparator is "=", "<" or "<=" and next comparator is <" or <=",
                 // discard the current constraint
-                if matches!(current.comparator, Equal | LessThan | LessThanOrEqual)
-                    && matches!(next.comparator, LessThan | LessThanOrEqual) {
+                if (current.comparator == Equal || current.comparator.is_upper_bound())
+                    && next.comparator.is_upper_bound() {
                     // Previous constraint becomes current if it exists
                     if let Some(previous) = filtered_constraints.pop() {
                         other_constraints.push_front(previous);
@@ -284,16 +1468,16 @@ impl<V : VT> GenericVersionRange<V> {
                 if let Some(previous) = filtered_constraints.last() {
                     // If the previous comparator is ">" or ">=" and current comparator
                     // is "=", ">" or ">=", discard the current constraint
-                    if matches!(previous.comparator, GreaterThan | GreaterThanOrEqual)
-                        && matches!(current.comparator, GreaterThan | GreaterThanOrEqual | Equal) {
+                    if previous.comparator.is_lower_bound()
+                        && (current.comparator.is_lower_bound() || current.comparator == Equal) {
                         // Discard the current constraint
                         continue;
                     }
 
                     // If the previous comparator is "=", "<" or "<=" and current comparator
                     // is "<" or "<=", discard the previous constraint
-                    if matches!(previous.comparator, Equal | LessThan | LessThanOrEqual)
-                        && matches!(current.comparator, LessThan | LessThanOrEqual) {
+                    if (previous.comparator == Equal || previous.comparator.is_upper_bound())
+                        && current.comparator.is_upper_bound() {
                         // Discard the previous constraint
                         filtered_constraints.pop();
                     }
@@ -311,15 +1495,15 @@ impl<V : VT> GenericVersionRange<V> {
             .map(|c| c.comparator)
             .peekable();
         while let Some(current) = filter_iter.next() {
-            if let Some(next) = filter_iter.peek() {
-                if current == Equal && !matches!(*next, Equal | GreaterThan | GreaterThanOrEqual) {
-                    return Err(VersError::InvalidRange(format!(
-                        "\"{}\" must not be followed by \"{}\" in a normalized range \
-                        (ignoring \"!=\")",
-                        current,
-                        next,
-                    )))
-                }
+            if let Some(next) = filter_iter.peek()
+                && current == Equal
+                && !matches!(*next, Equal | GreaterThan | GreaterThanOrEqual) {
+                return Err(VersError::InvalidRange(format!(
+                    "\"{}\" must not be followed by \"{}\" in a normalized range \
+                    (ignoring \"!=\")",
+                    current,
+                    next,
+                )))
             }
         }
 
@@ -372,95 +1556,438 @@ impl<V : VT> GenericVersionRange<V> {
 
         Ok(())
     }
-}
 
-impl<V : VT> FromStr for GenericVersionRange<V> {
-    type Err = VersError;
-    
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // Remove all spaces and tabs
-        let s = s.replace(|c: char| c.is_whitespace(), "");
-        
-        // Split on colon
-        let parts: Vec<&str> = s.splitn(2, ':').collect();
-        if parts.len() != 2 {
-            return Err(VersError::InvalidScheme);
+    /// Add a `=` constraint for every pre-release version that lost its constraint during
+    /// normalization, if the range still contains it and no other constraint admits
+    /// pre-releases of its release.
+    ///
+    /// Such a constraint lies within an interval of the range, so it adds no version to it,
+    /// but keeps matching the pre-releases the original constraint admitted, e.g. for
+    /// `<=1.5.0|1.5.0-rc.1`, which normalizes to `1.5.0-rc.1|<=1.5.0`.
+    fn keep_prerelease_anchors(&mut self, prereleases: Vec<V>) -> Result<(), VersError> {
+        let intervals = interval::from_constraints(&self.constraints);
+        let mut anchors: Vec<VersionConstraint<V>> = Vec::new();
+        for version in prereleases {
+            let admitted = self.constraints.iter().chain(&anchors).any(|c| c.version.allows_prerelease(&version));
+            if !admitted && intervals.iter().any(|i| interval::interval_contains(i, &version)) {
+                anchors.push(VersionConstraint::new(Equal, version));
+            }
         }
-        
-        // Validate URI scheme
-        let scheme = parts[0];
-        if scheme != "vers" {
-            return Err(VersError::InvalidScheme);
+        if anchors.is_empty() {
+            return Ok(());
         }
-        
-        // Split on slash
-        let specifier_parts: Vec<&str> = parts[1].splitn(2, '/').collect();
-        if specifier_parts.len() != 2 {
-            return Err(VersError::MissingVersioningScheme);
+
+        // A star must stand alone, so it cannot keep pre-releases admitted
+        if self.is_any() {
+            return Err(VersError::InvalidRange(
+                "Range of every version cannot keep admitting pre-releases".to_string(),
+            ));
         }
-        
-        // Get versioning scheme
-        let versioning_scheme = specifier_parts[0].to_lowercase();
-        if versioning_scheme.is_empty() {
-            return Err(VersError::MissingVersioningScheme);
+        self.constraints.extend(anchors);
+        self.constraints.sort_by(|a, b| a.version.cmp(&b.version));
+        Ok(())
+    }
+
+    /// Assert that this normalized range contains exactly the same constraint versions as the
+    /// constraint list it was normalized from.
+    ///
+    /// The original list is interpreted independently of the simplification algorithm, as the
+    /// intervals of `interval::from_unnormalized` with the `!=` versions removed. Pre-release
+    /// versions are sampled as well, and expected to be contained only if pre-releases are
+    /// allowed or one of the original constraints admits them.
+    #[cfg(debug_assertions)]
+    fn debug_assert_same_membership(&self, mut original: Vec<VersionConstraint<V>>) {
+        original.sort_by(|a, b| a.version.cmp(&b.version));
+        let intervals = interval::from_unnormalized(&original);
+
+        let only_exclusions = original.iter().all(|c| c.comparator == NotEqual);
+        for version in original.iter().map(|c| &c.version).filter(|v| v.is_comparable()) {
+            let admitted = !version.is_prerelease()
+                || self.allow_prereleases
+                || original.iter().any(|c| c.version.allows_prerelease(version));
+            let expected = if original.iter().any(|c| c.comparator == NotEqual && &c.version == version) {
+                false
+            } else {
+                admitted && (only_exclusions || intervals.iter().any(|i| interval::interval_contains(i, version)))
+            };
+            assert_eq!(
+                self.contains(version).ok(),
+                Some(expected),
+                "normalization changed the membership of {} in {}",
+                version,
+                self,
+            );
         }
-        
-        // Get constraint string
-        let constraints_str = specifier_parts[1].trim();
+    }
+}
+
+impl<V : VT> GenericVersionRange<V> {
+    /// Parse a version range specifier string using the given parse options.
+    ///
+    /// Plain `FromStr` parsing is equivalent to using the default options.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The version range specifier string to parse
+    /// * `options` - The options controlling the parsing
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the parsed and normalized range or an error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::{GenericVersionRange, ParseOptions};
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let options = ParseOptions { strict: true, ..Default::default() };
+    /// let result = GenericVersionRange::<SemVer>::from_str_with_options("vers:npm/*||", &options);
+    /// assert!(result.is_err());
+    /// ```
+    pub fn from_str_with_options(s: &str, options: &ParseOptions) -> Result<Self, VersError> {
+        let (versioning_scheme, constraints_str) = split_specifier(s)?;
+        Self::check_scheme_supported(&versioning_scheme)?;
+
+        // Check constraint string
         if constraints_str.is_empty() {
             return Err(VersError::EmptyConstraints);
         }
         
         // Handle star constraint
         if constraints_str == "*" {
-            return Ok(Self {
+            return Ok(Self::new(
                 versioning_scheme,
-                constraints: vec![VersionConstraint::new(Any, V::default())],
-            });
+                vec![VersionConstraint::new(Any, V::default())],
+            ));
         }
         
-        // Split constraints on each pipe
-        let constraint_strs: Vec<&str> = constraints_str
-            .trim_matches('|')
-            .split('|')
-            .filter(|s| !s.is_empty())
+        // Split constraints on each separator, ignoring empty constraints unless parsing strictly
+        let constraint_strs: Vec<(usize, &str)> = constraints_str
+            .split(options.constraint_separator)
+            .map(|s| s.trim())
+            .enumerate()
+            .filter(|(_, s)| options.strict || !s.is_empty())
             .collect();
         
         if constraint_strs.is_empty() {
             return Err(VersError::EmptyConstraints);
         }
+        if let Some(max_constraints) = options.max_constraints
+            && constraint_strs.len() > max_constraints {
+            return Err(VersError::TooManyConstraints(max_constraints));
+        }
 
-        // Parse each constraint
+        // Parse each constraint, expanding wildcards into a pair of constraints
         let mut constraints = Vec::new();
-        for constraint_str in constraint_strs {
-            let constraint = VersionConstraint::<V>::parse(constraint_str)?;
-            constraints.push(constraint);
+        for (index, constraint_str) in constraint_strs {
+            let parsed = Self::expand_wildcard(constraint_str, options.decode_percent, &versioning_scheme)
+                .unwrap_or_else(|| {
+                    VersionConstraint::<V>::parse_with_decoding(
+                        constraint_str,
+                        options.decode_percent,
+                        Some(&versioning_scheme),
+                    )
+                    .map(|c| vec![c])
+                })
+                .and_then(|parsed| if options.exclude_prereleases {
+                    parsed.into_iter().map(Self::round_to_stable).collect()
+                } else {
+                    Ok(parsed)
+                })
+                .map_err(|e| VersError::ConstraintParseError { index, source: Box::new(e) })?;
+            constraints.extend(parsed);
         }
         
-        let mut range = Self { versioning_scheme, constraints };
-        range.normalize_and_validate()?;  // Use the combined function
-        
-        Ok(range)
+        Self::try_from_parts(versioning_scheme, constraints)
+    }
+
+    /// Expand a constraint with a `.*` wildcard suffix, such as `1.2.*`, into the equivalent
+    /// pair of constraints `>=1.2|<1.3`, as described for `VT::wildcard_upper`.
+    ///
+    /// Returns `None` if the constraint has no wildcard or the version type does not support
+    /// wildcards, so that it is parsed as usual.
+    fn expand_wildcard(
+        constraint_str: &str,
+        decode_percent: bool,
+        versioning_scheme: &str,
+    ) -> Option<Result<Vec<VersionConstraint<V>>, VersError>> {
+        let prefix = constraint_str.strip_suffix(".*")?;
+        let constraint =
+            VersionConstraint::<V>::parse_with_decoding(prefix, decode_percent, Some(versioning_scheme)).ok()?;
+        let upper = constraint.version.wildcard_upper()?;
+        if constraint.comparator != Equal {
+            return Some(Err(VersError::InvalidConstraint(format!(
+                "Wildcard only supported with equality: {}",
+                constraint_str
+            ))));
+        }
+        Some(Ok(vec![
+            VersionConstraint::new(GreaterThanOrEqual, constraint.version),
+            VersionConstraint::new(LessThan, upper),
+        ]))
+    }
+
+    /// Round a constraint on a pre-release to the equivalent constraint on stable versions,
+    /// as described for `ParseOptions::exclude_prereleases`.
+    fn round_to_stable(constraint: VersionConstraint<V>) -> Result<VersionConstraint<V>, VersError> {
+        if !constraint.version.is_prerelease() {
+            return Ok(constraint);
+        }
+        let release = constraint.version.to_release();
+        match constraint.comparator {
+            GreaterThan | GreaterThanOrEqual => Ok(VersionConstraint::new(GreaterThanOrEqual, release)),
+            LessThan | LessThanOrEqual => Ok(VersionConstraint::new(LessThan, release)),
+            Equal | NotEqual | Any => Err(VersError::InvalidConstraint(format!(
+                "Pre-release version excluded: {}{}",
+                constraint.comparator, constraint.version
+            ))),
+        }
+    }
+}
+
+impl<V : VT> AsRef<[VersionConstraint<V>]> for GenericVersionRange<V> {
+    fn as_ref(&self) -> &[VersionConstraint<V>] {
+        &self.constraints
+    }
+}
+
+/// Intersects two ranges, as `intersect`.
+///
+/// # Panics
+///
+/// Panics if the ranges use different versioning schemes. Use `intersect` to handle this
+/// case as an error.
+impl<V : VT> BitAnd for &GenericVersionRange<V> {
+    type Output = GenericVersionRange<V>;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        self.intersect(other).expect("ranges must use the same versioning scheme")
+    }
+}
+
+/// Intersects two ranges, as `intersect`.
+///
+/// # Panics
+///
+/// Panics if the ranges use different versioning schemes.
+impl<V : VT> BitAnd for GenericVersionRange<V> {
+    type Output = GenericVersionRange<V>;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        &self & &other
+    }
+}
+
+/// Unites two ranges, as `union`.
+///
+/// # Panics
+///
+/// Panics if the ranges use different versioning schemes. Use `union` to handle this case
+/// as an error.
+impl<V : VT> BitOr for &GenericVersionRange<V> {
+    type Output = GenericVersionRange<V>;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        self.union(other).expect("ranges must use the same versioning scheme")
+    }
+}
+
+/// Unites two ranges, as `union`.
+///
+/// # Panics
+///
+/// Panics if the ranges use different versioning schemes.
+impl<V : VT> BitOr for GenericVersionRange<V> {
+    type Output = GenericVersionRange<V>;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        &self | &other
     }
 }
 
+impl<V : VT> FromStr for GenericVersionRange<V> {
+    type Err = VersError;
+    
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_str_with_options(s, &ParseOptions::default())
+    }
+}
+
+/// Compares a version range against a vers string, by parsing the string into its
+/// normalized form. A string that fails to parse is never equal to a range.
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::GenericVersionRange;
+/// use vers_rs::schemes::semver::SemVer;
+///
+/// let range: GenericVersionRange<SemVer> = "vers:npm/<2.0.0|>=1.0.0".parse().unwrap();
+/// assert!(range == "vers:npm/>=1.0.0|<2.0.0");
+/// ```
+impl<V : VT> PartialEq<str> for GenericVersionRange<V> {
+    fn eq(&self, other: &str) -> bool {
+        match other.parse::<GenericVersionRange<V>>() {
+            Ok(other) => self.versioning_scheme == other.versioning_scheme
+                && self.constraints == other.constraints,
+            Err(_) => false,
+        }
+    }
+}
+
+impl<V : VT> PartialEq<&str> for GenericVersionRange<V> {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+/// Characters percent-encoded in versions by the default `Display` form: only those that
+/// would otherwise be mistaken for structure when parsing the output again.
+const MINIMAL_ENCODE_SET: &AsciiSet = &CONTROLS.add(b'%').add(b'|');
+
+/// Characters percent-encoded in versions by the alternate `Display` form: everything
+/// outside the URI unreserved set.
+const PORTABLE_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Formats a version range as a vers string.
+///
+/// The default form (`{}`) only percent-encodes characters that are structural in vers,
+/// keeping the output human-readable. The alternate form (`{:#}`) percent-encodes every
+/// character outside the URI unreserved set, for embedding in contexts with strict escaping.
 impl<V : VT> Display for GenericVersionRange<V> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encode_set = if f.alternate() { PORTABLE_ENCODE_SET } else { MINIMAL_ENCODE_SET };
+        self.write_vers(f, encode_set, false)
+    }
+}
+
+/// Formats a version range in interval notation, e.g. `npm: [1.0.0, 2.0.0) \ {1.5.0}`.
+///
+/// Intervals are joined with `∪`, and `!=` exclusions follow a `\`. The alternate form
+/// (`{:#?}`) lists the fields of the range instead.
+impl<V : VT> fmt::Debug for GenericVersionRange<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            return f
+                .debug_struct("GenericVersionRange")
+                .field("versioning_scheme", &self.versioning_scheme)
+                .field("constraints", &self.constraints)
+                .field("allow_prereleases", &self.allow_prereleases)
+                .finish();
+        }
+
+        write!(f, "{}: ", self.versioning_scheme)?;
+        let intervals = self.to_intervals();
+        if intervals.is_empty() {
+            return write!(f, "∅");
+        }
+        for (i, (lower, upper)) in intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ∪ ")?;
+            }
+            match (lower, upper) {
+                (Bound::Included(l), Bound::Included(u)) if l == u => write!(f, "{{{}}}", l)?,
+                _ => {
+                    match lower {
+                        Bound::Included(l) => write!(f, "[{}, ", l)?,
+                        Bound::Excluded(l) => write!(f, "({}, ", l)?,
+                        Bound::Unbounded => write!(f, "(-∞, ")?,
+                    }
+                    match upper {
+                        Bound::Included(u) => write!(f, "{}]", u)?,
+                        Bound::Excluded(u) => write!(f, "{})", u)?,
+                        Bound::Unbounded => write!(f, "+∞)")?,
+                    }
+                }
+            }
+        }
+
+        let exclusions = self.exclusions();
+        if !exclusions.is_empty() {
+            let exclusions: Vec<String> = exclusions.iter().map(|v| v.to_string()).collect();
+            write!(f, " \\ {{{}}}", exclusions.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl<V : VT> GenericVersionRange<V> {
+    /// Format this range as a vers string that writes the `=` comparator of equality
+    /// constraints explicitly, e.g. `vers:npm/=1.2.3|=2.0.0`.
+    ///
+    /// The default `Display` form omits `=`, which is the canonical form. Both forms parse
+    /// to the same range.
+    ///
+    /// # Returns
+    ///
+    /// The vers string with explicit equality comparators
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/=1.2.3|=2.0.0".parse().unwrap();
+    /// assert_eq!(range.to_string(), "vers:npm/1.2.3|2.0.0");
+    /// assert_eq!(range.to_string_explicit(), "vers:npm/=1.2.3|=2.0.0");
+    /// ```
+    pub fn to_string_explicit(&self) -> String {
+        let mut s = String::new();
+        // Writing to a String cannot fail
+        let _ = self.write_vers(&mut s, MINIMAL_ENCODE_SET, true);
+        s
+    }
+
+    /// Write this range as a vers string, percent-encoding versions with the given set.
+    fn write_vers(
+        &self,
+        f: &mut impl fmt::Write,
+        encode_set: &'static AsciiSet,
+        explicit_equal: bool,
+    ) -> fmt::Result {
         write!(f, "vers:{}/", self.versioning_scheme)?;
 
-        match self.constraints[0].comparator {
-            Any => write!(f, "*")?,
-            Equal => write!(f, "{}", self.constraints[0].version)?,
-            _ => write!(f, "{}{}", self.constraints[0].comparator, self.constraints[0].version)?,
+        // An empty range has no constraints to write
+        if self.constraints.is_empty() {
+            return Ok(());
         }
 
-        for constraint in &self.constraints[1..] {
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            if i > 0 {
+                write!(f, "|")?;
+            }
+            let version = constraint.version.to_string();
+            let version = utf8_percent_encode(&version, encode_set);
             match constraint.comparator {
-                Equal => write!(f, "|{}", constraint.version)?,
-                _ => write!(f, "|{}{}", constraint.comparator, constraint.version)?,
+                Any => write!(f, "*")?,
+                Equal if !explicit_equal => write!(f, "{}", version)?,
+                _ => write!(f, "{}{}", constraint.comparator, version)?,
             }
         }
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Serializes a version range as its vers string.
+#[cfg(feature = "serde")]
+impl<V : VT> serde::Serialize for GenericVersionRange<V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Deserializes a version range from its vers string.
+#[cfg(feature = "serde")]
+impl<'de, V : VT> serde::Deserialize<'de> for GenericVersionRange<V> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
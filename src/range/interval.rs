@@ -0,0 +1,373 @@
+//! Interval representation of version ranges for the vers-rs library.
+//!
+//! This module converts between the constraint list of a normalized version range and
+//! a sorted list of disjoint intervals, and implements the set operations on those
+//! interval lists that back range algebra such as `difference`.
+
+use crate::comparator::Comparator::*;
+use crate::constraint::VT;
+use crate::VersionConstraint;
+use std::cmp::Ordering;
+use std::ops::Bound;
+use std::ops::Bound::*;
+
+/// A contiguous interval of versions, given as its lower and upper bound.
+pub type Interval<V> = (Bound<V>, Bound<V>);
+
+/// Compare two lower bounds. `Unbounded` is the smallest lower bound, and an inclusive
+/// bound starts before an exclusive bound on the same version.
+fn cmp_lower<V: VT>(a: &Bound<V>, b: &Bound<V>) -> Ordering {
+    match (a, b) {
+        (Unbounded, Unbounded) => Ordering::Equal,
+        (Unbounded, _) => Ordering::Less,
+        (_, Unbounded) => Ordering::Greater,
+        (Included(x), Included(y)) | (Excluded(x), Excluded(y)) => x.cmp(y),
+        (Included(x), Excluded(y)) => x.cmp(y).then(Ordering::Less),
+        (Excluded(x), Included(y)) => x.cmp(y).then(Ordering::Greater),
+    }
+}
+
+/// Compare two upper bounds. `Unbounded` is the largest upper bound, and an exclusive
+/// bound ends before an inclusive bound on the same version.
+fn cmp_upper<V: VT>(a: &Bound<V>, b: &Bound<V>) -> Ordering {
+    match (a, b) {
+        (Unbounded, Unbounded) => Ordering::Equal,
+        (Unbounded, _) => Ordering::Greater,
+        (_, Unbounded) => Ordering::Less,
+        (Included(x), Included(y)) | (Excluded(x), Excluded(y)) => x.cmp(y),
+        (Included(x), Excluded(y)) => x.cmp(y).then(Ordering::Greater),
+        (Excluded(x), Included(y)) => x.cmp(y).then(Ordering::Less),
+    }
+}
+
+/// Turn the bound ending one interval into the bound starting the adjacent gap, and vice versa.
+fn flip<V: VT>(bound: &Bound<V>) -> Bound<V> {
+    match bound {
+        Included(v) => Excluded(v.clone()),
+        Excluded(v) => Included(v.clone()),
+        Unbounded => Unbounded,
+    }
+}
+
+/// Check whether an interval contains no version at all.
+pub(crate) fn is_empty_interval<V: VT>(lower: &Bound<V>, upper: &Bound<V>) -> bool {
+    match (lower, upper) {
+        (Included(l), Included(u)) => l > u,
+        (Included(l), Excluded(u)) | (Excluded(l), Included(u)) | (Excluded(l), Excluded(u)) => l >= u,
+        _ => false,
+    }
+}
+
+/// Check whether an interval ending at `upper` and one starting at `lower` overlap or touch,
+/// so that together they form a single contiguous interval.
+fn connects<V: VT>(upper: &Bound<V>, lower: &Bound<V>) -> bool {
+    match (upper, lower) {
+        (Unbounded, _) | (_, Unbounded) => true,
+        (Excluded(u), Excluded(l)) => l < u,
+        (Included(u) | Excluded(u), Included(l) | Excluded(l)) => l <= u,
+    }
+}
+
+/// Check whether a version lies below the lower bound of an interval.
+pub(crate) fn is_below<V: VT>(interval: &Interval<V>, version: &V) -> bool {
+    match &interval.0 {
+        Included(l) => version < l,
+        Excluded(l) => version <= l,
+        Unbounded => false,
+    }
+}
+
+/// Check whether a version lies above the upper bound of an interval.
+pub(crate) fn is_above<V: VT>(interval: &Interval<V>, version: &V) -> bool {
+    match &interval.1 {
+        Included(u) => version > u,
+        Excluded(u) => version >= u,
+        Unbounded => false,
+    }
+}
+
+/// Check whether an interval contains a release, i.e. a version that is not a pre-release.
+///
+/// If the version type cannot tell the next release (see `VT::next_release`), any
+/// non-empty interval is assumed to contain one.
+pub(crate) fn contains_release<V: VT>(interval: &Interval<V>) -> bool {
+    let first_release = match &interval.0 {
+        Unbounded => return true,
+        Included(l) if !l.is_prerelease() => Some(l.clone()),
+        Included(l) | Excluded(l) => l.next_release(),
+    };
+    match first_release {
+        Some(release) => !is_above(interval, &release),
+        None => !is_empty_interval(&interval.0, &interval.1),
+    }
+}
+
+/// Check whether a version lies within an interval.
+pub(crate) fn interval_contains<V: VT>(interval: &Interval<V>, version: &V) -> bool {
+    !is_below(interval, version) && !is_above(interval, version)
+}
+
+/// Compute the intervals of a normalized constraint list, ignoring `!=` exclusions.
+///
+/// `=` constraints become single-version intervals, and the ordered comparators are paired
+/// into intervals. A constraint list consisting of `*` or only `!=` constraints yields the
+/// unbounded interval, and an empty constraint list yields no interval.
+pub(crate) fn bounds<V: VT>(constraints: &[VersionConstraint<V>]) -> Vec<Interval<V>> {
+    if constraints.is_empty() {
+        return Vec::new();
+    }
+    if constraints.iter().all(|c| matches!(c.comparator, Any | NotEqual)) {
+        return vec![(Unbounded, Unbounded)];
+    }
+
+    let mut intervals = Vec::new();
+    let mut lower: Option<Bound<V>> = None;
+    let mut first_bound = true;
+    for constraint in constraints {
+        let version = constraint.version.clone();
+        match constraint.comparator {
+            Equal => intervals.push((Included(version.clone()), Included(version))),
+            GreaterThan => lower = Some(Excluded(version)),
+            GreaterThanOrEqual => lower = Some(Included(version)),
+            LessThan | LessThanOrEqual => {
+                let upper = if constraint.comparator == LessThan {
+                    Excluded(version)
+                } else {
+                    Included(version)
+                };
+                match lower.take() {
+                    Some(lower) => intervals.push((lower, upper)),
+                    None if first_bound => intervals.push((Unbounded, upper)),
+                    None => {}
+                }
+            }
+            NotEqual | Any => continue,
+        }
+        if constraint.comparator != Equal {
+            first_bound = false;
+        }
+    }
+    if let Some(lower) = lower {
+        intervals.push((lower, Unbounded));
+    }
+
+    normalize(intervals)
+}
+
+/// Compute the intervals of a sorted constraint list that is not normalized, ignoring `!=`
+/// exclusions.
+///
+/// This follows the simplification of the spec: a run of lower bounds starts at its first
+/// bound and a run of upper bounds ends at its last bound, while `=` constraints become
+/// single-version intervals. Bounds on the same version are read in their order in the list,
+/// so `>=1.0.0|<=1.0.0` is the single version `1.0.0`, while `<=1.0.0|>=1.0.0` is every
+/// version. The resulting intervals are normalized.
+pub(crate) fn from_unnormalized<V: VT>(constraints: &[VersionConstraint<V>]) -> Vec<Interval<V>> {
+    let mut intervals = Vec::new();
+    let mut lower: Option<Bound<V>> = None;
+    let mut upper: Option<Bound<V>> = None;
+    for constraint in constraints {
+        let version = constraint.version.clone();
+        match constraint.comparator {
+            GreaterThan | GreaterThanOrEqual => {
+                if let Some(upper) = upper.take() {
+                    intervals.push((lower.take().unwrap_or(Unbounded), upper));
+                }
+                if lower.is_none() {
+                    lower = Some(if constraint.comparator == GreaterThan {
+                        Excluded(version)
+                    } else {
+                        Included(version)
+                    });
+                }
+            }
+            LessThan => upper = Some(Excluded(version)),
+            LessThanOrEqual => upper = Some(Included(version)),
+            Equal => intervals.push((Included(version.clone()), Included(version))),
+            NotEqual => {}
+            Any => intervals.push((Unbounded, Unbounded)),
+        }
+    }
+    match (lower, upper) {
+        (lower, Some(upper)) => intervals.push((lower.unwrap_or(Unbounded), upper)),
+        (Some(lower), None) => intervals.push((lower, Unbounded)),
+        (None, None) => {}
+    }
+    normalize(intervals)
+}
+
+/// Remove single versions from a list of intervals, splitting every interval containing one.
+pub(crate) fn exclude<V: VT>(intervals: Vec<Interval<V>>, excluded: &[&V]) -> Vec<Interval<V>> {
+    let mut result = intervals;
+    for version in excluded {
+        result = result
+            .into_iter()
+            .flat_map(|interval| {
+                if interval_contains(&interval, version) {
+                    vec![
+                        (interval.0, Excluded((*version).clone())),
+                        (Excluded((*version).clone()), interval.1),
+                    ]
+                } else {
+                    vec![interval]
+                }
+            })
+            .filter(|(lower, upper)| !is_empty_interval(lower, upper))
+            .collect();
+    }
+    result
+}
+
+/// Compute the disjoint intervals of a normalized constraint list, with every `!=` exclusion
+/// resolved into a split of the interval containing it.
+pub(crate) fn from_constraints<V: VT>(constraints: &[VersionConstraint<V>]) -> Vec<Interval<V>> {
+    let excluded: Vec<&V> = constraints
+        .iter()
+        .filter(|c| c.comparator == NotEqual)
+        .map(|c| &c.version)
+        .collect();
+    exclude(bounds(constraints), &excluded)
+}
+
+/// Sort a list of intervals and merge all overlapping or touching intervals, dropping empty ones.
+pub(crate) fn normalize<V: VT>(intervals: Vec<Interval<V>>) -> Vec<Interval<V>> {
+    let mut intervals: Vec<Interval<V>> = intervals
+        .into_iter()
+        .filter(|(lower, upper)| !is_empty_interval(lower, upper))
+        .collect();
+    intervals.sort_by(|a, b| cmp_lower(&a.0, &b.0));
+
+    let mut merged: Vec<Interval<V>> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        if let Some(last) = merged.last_mut()
+            && connects(&last.1, &interval.0) {
+            if cmp_upper(&interval.1, &last.1) == Ordering::Greater {
+                last.1 = interval.1;
+            }
+            continue;
+        }
+        merged.push(interval);
+    }
+    merged
+}
+
+/// Compute the intersection of two normalized interval lists.
+pub(crate) fn intersect<V: VT>(a: &[Interval<V>], b: &[Interval<V>]) -> Vec<Interval<V>> {
+    let mut result = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let lower = match cmp_lower(&a[i].0, &b[j].0) {
+            Ordering::Less => &b[j].0,
+            _ => &a[i].0,
+        };
+        let (upper, advance_a) = match cmp_upper(&a[i].1, &b[j].1) {
+            Ordering::Greater => (&b[j].1, false),
+            _ => (&a[i].1, true),
+        };
+        if !is_empty_interval(lower, upper) {
+            result.push((lower.clone(), upper.clone()));
+        }
+        if advance_a {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    result
+}
+
+/// Check whether two normalized interval lists have any version in common.
+pub(crate) fn intersects<V: VT>(a: &[Interval<V>], b: &[Interval<V>]) -> bool {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let lower = match cmp_lower(&a[i].0, &b[j].0) {
+            Ordering::Less => &b[j].0,
+            _ => &a[i].0,
+        };
+        let (upper, advance_a) = match cmp_upper(&a[i].1, &b[j].1) {
+            Ordering::Greater => (&b[j].1, false),
+            _ => (&a[i].1, true),
+        };
+        if !is_empty_interval(lower, upper) {
+            return true;
+        }
+        if advance_a {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    false
+}
+
+/// Check whether a normalized interval list covers every version of an interval.
+///
+/// As the intervals of a normalized list neither overlap nor touch, the interval must lie
+/// within a single one of them.
+pub(crate) fn covers<V: VT>(intervals: &[Interval<V>], interval: &Interval<V>) -> bool {
+    intervals.iter().any(|(lower, upper)| {
+        cmp_lower(lower, &interval.0) != Ordering::Greater
+            && cmp_upper(upper, &interval.1) != Ordering::Less
+    })
+}
+
+/// Compute the complement of a normalized interval list.
+pub(crate) fn complement<V: VT>(intervals: &[Interval<V>]) -> Vec<Interval<V>> {
+    let mut result = Vec::new();
+    let mut start = Unbounded;
+    for (lower, upper) in intervals {
+        if *lower != Unbounded {
+            let gap = (start, flip(lower));
+            if !is_empty_interval(&gap.0, &gap.1) {
+                result.push(gap);
+            }
+        }
+        if *upper == Unbounded {
+            return result;
+        }
+        start = flip(upper);
+    }
+    result.push((start, Unbounded));
+    result
+}
+
+/// Compute the constraint list of a normalized interval list.
+///
+/// Two intervals separated by exactly one excluded version are expressed as a `!=`
+/// constraint, so the result is a valid normalized constraint list.
+pub(crate) fn to_constraints<V: VT>(intervals: &[Interval<V>]) -> Vec<VersionConstraint<V>> {
+    let mut constraints: Vec<VersionConstraint<V>> = Vec::new();
+    for (lower, upper) in intervals {
+        if let (Included(l), Included(u)) = (lower, upper)
+            && l == u {
+            constraints.push(VersionConstraint::new(Equal, l.clone()));
+            continue;
+        }
+
+        match lower {
+            Included(l) => constraints.push(VersionConstraint::new(GreaterThanOrEqual, l.clone())),
+            Excluded(l) => {
+                // A gap of a single version between two intervals is a "!=" exclusion
+                match constraints.last() {
+                    Some(last) if last.comparator == LessThan && last.version == *l => {
+                        constraints.pop();
+                        constraints.push(VersionConstraint::new(NotEqual, l.clone()));
+                    }
+                    _ => constraints.push(VersionConstraint::new(GreaterThan, l.clone())),
+                }
+            }
+            Unbounded => {}
+        }
+
+        match upper {
+            Included(u) => constraints.push(VersionConstraint::new(LessThanOrEqual, u.clone())),
+            Excluded(u) => constraints.push(VersionConstraint::new(LessThan, u.clone())),
+            Unbounded => {}
+        }
+    }
+
+    if !intervals.is_empty() && constraints.is_empty() {
+        constraints.push(VersionConstraint::new(Any, V::default()));
+    }
+    constraints
+}
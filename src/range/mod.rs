@@ -1,11 +1,111 @@
 use crate::{VersError, VersionConstraint};
 use crate::constraint::VT;
+use std::borrow::Cow;
+
+/// Split a version range specifier into its versioning scheme and its constraints string.
+///
+/// Whitespace is only stripped around the structural separators, so that it is never
+/// removed from within a version. A specifier wrapped in matching single or double quotes,
+/// as commonly found in YAML or TOML sources, is unquoted. A URL-like `//` after `vers:`,
+/// as in `vers://npm/1.2.3`, is tolerated. The versioning scheme is lowercased, and must then
+/// consist of lowercase ASCII letters, digits, `.`, `-` and `+`.
+pub(crate) fn split_specifier(s: &str) -> Result<(String, &str), VersError> {
+    // Strip surrounding matched quotes
+    let s = s.trim();
+    let s = ['"', '\'']
+        .iter()
+        .find_map(|q| s.strip_prefix(*q).and_then(|s| s.strip_suffix(*q)))
+        .unwrap_or(s);
+
+    // Split on colon
+    let (scheme, specifier) = s.split_once(':').ok_or(VersError::InvalidScheme)?;
+
+    // Validate URI scheme
+    if scheme.trim() != "vers" {
+        return Err(VersError::InvalidScheme);
+    }
+
+    // Tolerate a URL-like double slash after the URI scheme
+    let specifier = specifier.strip_prefix("//").unwrap_or(specifier);
+
+    // Split on slash
+    let (versioning_scheme, constraints) = specifier
+        .split_once('/')
+        .ok_or(VersError::MissingVersioningScheme)?;
+
+    // Get versioning scheme
+    let versioning_scheme = versioning_scheme.trim().to_lowercase();
+    if versioning_scheme.is_empty() {
+        return Err(VersError::MissingVersioningScheme);
+    }
+    let is_valid_char = |c: char| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '-' | '+');
+    if !versioning_scheme.chars().all(is_valid_char) {
+        return Err(VersError::InvalidVersioningScheme(versioning_scheme));
+    }
+
+    Ok((versioning_scheme, constraints.trim()))
+}
+
+/// The reason why a version is not contained within a version range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExclusionReason {
+    /// The version is below the lowest bound of the range.
+    BelowLowerBound,
+    /// The version is above the highest bound of the range.
+    AboveUpperBound,
+    /// The version lies between two intervals of the range.
+    InGap,
+    /// The version lies within an interval of the range, but is excluded by a `!=` constraint.
+    ExplicitlyExcluded,
+    /// The version is a pre-release, which the range does not admit (see
+    /// `GenericVersionRange::allow_prereleases`).
+    PrereleaseNotAllowed,
+}
+
+/// The relation between the sets of versions covered by two version ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeRelation {
+    /// The ranges have no version in common.
+    Disjoint,
+    /// The ranges cover the same versions.
+    Equal,
+    /// Every version of the first range is covered by the second range, but not vice versa.
+    Subset,
+    /// Every version of the second range is covered by the first range, but not vice versa.
+    Superset,
+    /// The ranges have versions in common, and each covers versions the other does not.
+    Overlapping,
+}
+
+/// A deviation of a version range from the canonical form of the vers specification.
+///
+/// Indices refer to the constraints of the range, in their stored order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanonicalViolation {
+    /// The range has no constraints.
+    EmptyConstraints,
+    /// The `*` constraint at the index is combined with other constraints.
+    StarNotAlone { index: usize },
+    /// The constraint at the index has a lower version than the preceding constraint.
+    Unsorted { index: usize },
+    /// The constraint at the index has the same version as the preceding constraint.
+    DuplicateVersion { index: usize },
+    /// The constraint at the index follows a `=` constraint (ignoring `!=`), but is not one
+    /// of `=`, `>` or `>=`.
+    DisallowedAfterEqual { index: usize },
+    /// The bound at the index is implied by a neighboring bound of the same direction
+    /// (ignoring `=` and `!=`), so the comparators do not alternate.
+    RedundantBound { index: usize },
+}
 
 pub trait VersionRange<V> {
     fn versioning_scheme(&self) -> &str;
     fn contains(&self, version: V) -> Result<bool, VersError>;
-    fn constraints(&self) -> &Vec<VersionConstraint<impl VT>>;
+    fn constraints(&self) -> Cow<'_, [VersionConstraint<impl VT>]>;
 }
 
 pub mod generic;
-pub mod dynamic;
\ No newline at end of file
+pub mod dynamic;
+pub mod interval;
+pub mod prepared;
+pub mod structural;
\ No newline at end of file
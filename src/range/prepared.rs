@@ -0,0 +1,66 @@
+//! Shared version range implementation for the vers-rs library.
+//!
+//! This module contains the `PreparedRange` struct, an immutable version range that is
+//! cheap to clone, for sharing one range across threads.
+
+use crate::constraint::VT;
+use crate::range::VersionRange;
+use crate::{GenericVersionRange, VersError, VersionConstraint};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// An immutable version range, validated once and shared behind an `Arc`.
+///
+/// Cloning a prepared range only increments a reference count, without copying its
+/// constraints, and it is `Send` and `Sync` if its version type is. Create one with
+/// `GenericVersionRange::into_prepared`.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+/// use vers_rs::GenericVersionRange;
+/// use vers_rs::range::VersionRange;
+/// use vers_rs::schemes::semver::SemVer;
+///
+/// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0|<2.0.0".parse().unwrap();
+/// let prepared = range.into_prepared().unwrap();
+///
+/// let worker = prepared.clone();
+/// let handle = thread::spawn(move || worker.contains(&"1.5.0".parse().unwrap()).unwrap());
+/// assert!(handle.join().unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedRange<V: VT> {
+    range: Arc<GenericVersionRange<V>>,
+}
+
+impl<V: VT> PreparedRange<V> {
+    /// Wrap a range that has already been validated for `contains`.
+    pub(crate) fn new(range: GenericVersionRange<V>) -> Self {
+        Self { range: Arc::new(range) }
+    }
+
+    /// Get the shared range.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the range
+    pub fn range(&self) -> &GenericVersionRange<V> {
+        &self.range
+    }
+}
+
+impl<V: VT> VersionRange<&V> for PreparedRange<V> {
+    fn versioning_scheme(&self) -> &str {
+        self.range.versioning_scheme()
+    }
+
+    fn contains(&self, version: &V) -> Result<bool, VersError> {
+        self.range.contains(version)
+    }
+
+    fn constraints(&self) -> Cow<'_, [VersionConstraint<impl VT>]> {
+        Cow::Borrowed(&self.range.constraints)
+    }
+}
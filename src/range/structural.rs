@@ -0,0 +1,101 @@
+//! Structural version range implementation for the vers-rs library.
+//!
+//! This module contains the `StructuralRange` struct, which validates the shape of a version
+//! range specifier without parsing its versions. It serves tooling that needs to check vers
+//! strings of versioning schemes not supported by this library.
+
+use crate::range::split_specifier;
+use crate::{VersError, VersionConstraint};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A version range specifier checked for its structure only, with raw constraint strings.
+///
+/// Parsing checks the URI scheme, the versioning scheme and the comparators of the
+/// constraints, but treats versions as opaque strings, so ranges of any versioning scheme
+/// are accepted. As versions cannot be compared, checking whether a version is contained
+/// fails with `VersError::MembershipUnsupported`.
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::range::dynamic::DynamicVersionRange;
+///
+/// let range = DynamicVersionRange::parse_structural("vers:pypi/>=1.0.0|<2.0.0").unwrap();
+/// assert_eq!(range.versioning_scheme, "pypi");
+/// assert_eq!(range.constraints, vec![">=1.0.0", "<2.0.0"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructuralRange {
+    /// The versioning scheme (e.g., "npm", "pypi", "maven", "deb")
+    pub versioning_scheme: String,
+
+    /// The constraint strings, trimmed and in their original order
+    pub constraints: Vec<String>,
+}
+
+impl StructuralRange {
+    /// Check if a version is contained within this range.
+    ///
+    /// Versions of a structural range cannot be compared, so this always fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `_version` - The version to check
+    ///
+    /// # Returns
+    ///
+    /// `VersError::MembershipUnsupported` with the versioning scheme of this range
+    pub fn contains(&self, _version: &str) -> Result<bool, VersError> {
+        Err(VersError::MembershipUnsupported(self.versioning_scheme.clone()))
+    }
+}
+
+impl FromStr for StructuralRange {
+    type Err = VersError;
+
+    /// Parse a version range specifier string into a `StructuralRange`.
+    ///
+    /// Empty constraints between separators are ignored, as when parsing typed ranges.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The version range specifier string to parse
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the parsed `StructuralRange` or an error
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (versioning_scheme, constraints_str) = split_specifier(s)?;
+        if constraints_str.is_empty() {
+            return Err(VersError::EmptyConstraints);
+        }
+
+        let mut constraints = Vec::new();
+        for (index, constraint_str) in constraints_str.split('|').map(|s| s.trim()).enumerate() {
+            if constraint_str.is_empty() {
+                continue;
+            }
+            // Parsing as opaque string versions checks the comparator and percent-encoding
+            VersionConstraint::<String>::parse(constraint_str)
+                .map_err(|e| VersError::ConstraintParseError { index, source: Box::new(e) })?;
+            constraints.push(constraint_str.to_string());
+        }
+
+        if constraints.is_empty() {
+            return Err(VersError::EmptyConstraints);
+        }
+        if constraints.len() > 1 && constraints.iter().any(|c| c == "*") {
+            return Err(VersError::InvalidRange("Star constraint must be used alone".to_string()));
+        }
+
+        Ok(Self { versioning_scheme, constraints })
+    }
+}
+
+impl Display for StructuralRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "vers:{}/{}", self.versioning_scheme, self.constraints.join("|"))
+    }
+}
@@ -0,0 +1,179 @@
+use crate::VersError;
+use crate::constraint::VT;
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub static CONDA_SCHEME: &str = "conda";
+
+/// A Conda package version, ordered following Conda's `VersionOrder`.
+///
+/// A version consists of an optional epoch (`1!`), the version proper and an optional local
+/// version (`+local`). The version and local version are split into components at `.` and
+/// `_`, and each component into runs of digits and letters, e.g. `1.1post1` into `1` and
+/// `1`, `post`, `1`. Components are compared one by one, with missing ones treated as zero,
+/// where:
+/// - numbers compare numerically and strings lexicographically, strings before numbers,
+///   so `1.1a1 < 1.1`
+/// - `dev` comes before any other string, so `1.1dev1 < 1.1a1`
+/// - `post` comes after any number, so `1.1 < 1.1post1`
+/// - a component starting with a letter is preceded by a zero, so `1.1.dev1 == 1.1.0dev1`
+///
+/// Versions are case-insensitive, and stored and displayed in lowercase.
+#[derive(Clone, Debug)]
+pub struct CondaVersion {
+    /// The version as written, lowercased
+    raw: String,
+    epoch: u64,
+    version: Vec<Vec<Part>>,
+    local: Vec<Vec<Part>>,
+}
+
+/// A run of digits or letters of a version component, in ascending order of the variants.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Part {
+    Dev,
+    Str(String),
+    Num(u64),
+    Post,
+}
+
+/// Compare two lists of components, padding the shorter ones with zeros.
+fn cmp_components(a: &[Vec<Part>], b: &[Vec<Part>]) -> Ordering {
+    const ZERO: Part = Part::Num(0);
+    (0..a.len().max(b.len()))
+        .flat_map(|i| {
+            let x = a.get(i).map_or(&[][..], Vec::as_slice);
+            let y = b.get(i).map_or(&[][..], Vec::as_slice);
+            (0..x.len().max(y.len()))
+                .map(move |j| x.get(j).unwrap_or(&ZERO).cmp(y.get(j).unwrap_or(&ZERO)))
+        })
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Parse the components of a version or local version.
+fn parse_components(s: &str) -> Result<Vec<Vec<Part>>, String> {
+    s.split(['.', '_'])
+        .map(|component| {
+            if component.is_empty() {
+                return Err("empty version component".to_string());
+            }
+            let mut parts = Vec::new();
+            let mut rest = component;
+            while let Some(first) = rest.chars().next() {
+                let is_digit = first.is_ascii_digit();
+                let end = rest.find(|c: char| c.is_ascii_digit() != is_digit).unwrap_or(rest.len());
+                let (run, tail) = rest.split_at(end);
+                parts.push(match run {
+                    _ if is_digit => Part::Num(run.parse().map_err(|e| format!("invalid number \"{}\": {}", run, e))?),
+                    "dev" => Part::Dev,
+                    "post" => Part::Post,
+                    _ => Part::Str(run.to_string()),
+                });
+                rest = tail;
+            }
+            // Keep numbers and strings in phase, e.g. for "1.1.a1" and "1.1.0a1"
+            if !component.starts_with(|c: char| c.is_ascii_digit()) {
+                parts.insert(0, Part::Num(0));
+            }
+            Ok(parts)
+        })
+        .collect()
+}
+
+impl Default for CondaVersion {
+    fn default() -> Self {
+        CondaVersion {
+            raw: "0".to_string(),
+            epoch: 0,
+            version: vec![vec![Part::Num(0)]],
+            local: Vec::new(),
+        }
+    }
+}
+
+impl PartialEq for CondaVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CondaVersion {}
+
+impl PartialOrd for CondaVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CondaVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch
+            .cmp(&other.epoch)
+            .then_with(|| cmp_components(&self.version, &other.version))
+            .then_with(|| cmp_components(&self.local, &other.local))
+    }
+}
+
+impl VT for CondaVersion {
+    const CANONICAL_SCHEMES: &'static [&'static str] = &[CONDA_SCHEME];
+
+    fn example() -> Self {
+        "1.2.3".parse().expect("valid example version")
+    }
+
+    /// Increment the last component of this version, e.g. `1.2` to `1.3`, which must be a
+    /// plain number. Versions with a local version are not supported.
+    fn wildcard_upper(&self) -> Option<Self> {
+        if !self.local.is_empty() {
+            return None;
+        }
+        let start = self.raw.rfind(['.', '_', '!']).map_or(0, |i| i + 1);
+        let (head, last) = self.raw.split_at(start);
+        let next = last.parse::<u64>().ok()?.checked_add(1)?;
+        format!("{}{}", head, next).parse().ok()
+    }
+}
+
+impl Display for CondaVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl FromStr for CondaVersion {
+    type Err = VersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let raw = s.trim().to_lowercase();
+        let invalid = |e: String| VersError::InvalidVersionFormat(CONDA_SCHEME, s.to_string(), e);
+
+        if raw.is_empty() {
+            return Err(invalid("empty version".to_string()));
+        }
+        if let Some(c) = raw.chars().find(|c| !matches!(c, 'a'..='z' | '0'..='9' | '.' | '_' | '+' | '!')) {
+            return Err(invalid(format!("invalid character '{}'", c)));
+        }
+
+        let (epoch, rest) = match raw.split_once('!') {
+            Some((epoch, rest)) => {
+                let epoch = epoch.parse::<u64>().map_err(|e| invalid(format!("invalid epoch \"{}\": {}", epoch, e)))?;
+                (epoch, rest)
+            }
+            None => (0, raw.as_str()),
+        };
+        let (version, local) = match rest.split_once('+') {
+            Some((version, local)) => (version, Some(local)),
+            None => (rest, None),
+        };
+        if rest.contains('!') || local.is_some_and(|local| local.contains('+')) {
+            return Err(invalid("duplicate epoch or local version separator".to_string()));
+        }
+        let version = parse_components(version).map_err(invalid)?;
+        let local = local.map(parse_components).transpose().map_err(invalid)?.unwrap_or_default();
+
+        Ok(CondaVersion { raw, epoch, version, local })
+    }
+}
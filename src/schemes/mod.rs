@@ -1 +1,153 @@
-pub mod semver;
\ No newline at end of file
+//! Versioning schemes supported by the vers-rs library.
+//!
+//! Each submodule provides a version type implementing the comparison rules
+//! of one or more versioning schemes.
+//!
+//! Parsing a version stores it in the canonical form of its scheme, which is what
+//! `Display` writes back: SemVer pads partial versions of npm ranges (`1.0` becomes
+//! `1.0.0`), and numeric versions drop leading zeros of their segments (`01.002` becomes
+//! `1.2`). Versions that are equal under the rules of their scheme compare equal even when
+//! written differently, e.g. the numeric versions `1.2` and `1.2.0`, so duplicate versions
+//! are detected either way.
+
+use crate::VersError;
+use crate::constraint::VT;
+use crate::schemes::conda::CondaVersion;
+use crate::schemes::numeric::NumericVersion;
+use crate::schemes::semver::SemVer;
+use std::collections::HashMap;
+
+pub mod conda;
+pub mod numeric;
+pub mod opaque;
+pub mod semver;
+
+/// Names of all versioning schemes supported by `DynamicVersionRange`.
+///
+/// Keep in sync with the dispatch in `DynamicVersionRange::from_str_with_options`, `validate`,
+/// `example_version` and `canonical_scheme`.
+static SUPPORTED_SCHEMES: &[&str] = &["conda", "npm", "numeric", "semver"];
+
+/// Get the names of all versioning schemes supported by this library.
+///
+/// These are exactly the schemes accepted by `DynamicVersionRange::from_str`.
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::schemes::supported_schemes;
+///
+/// assert!(supported_schemes().contains(&"npm"));
+/// ```
+pub fn supported_schemes() -> &'static [&'static str] {
+    SUPPORTED_SCHEMES
+}
+
+/// Get an example of a valid version for a versioning scheme.
+///
+/// # Arguments
+///
+/// * `scheme` - The versioning scheme (e.g., "npm", "semver")
+///
+/// # Returns
+///
+/// The example version, or `VersError::UnsupportedVersioningScheme` if the scheme is unknown
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::schemes::{example_version, validate};
+///
+/// let version = example_version("npm").unwrap();
+/// assert!(validate("npm", &version).is_ok());
+/// ```
+pub fn example_version(scheme: &str) -> Result<String, VersError> {
+    match scheme.to_lowercase().as_str() {
+        "semver" | "npm" => Ok(SemVer::example().to_string()),
+        "numeric" => Ok(NumericVersion::example().to_string()),
+        "conda" => Ok(CondaVersion::example().to_string()),
+        _ => Err(VersError::UnsupportedVersioningScheme(scheme.to_string())),
+    }
+}
+
+/// Get the default aliases of versioning schemes, mapping common synonyms to the scheme
+/// names supported by this library, e.g. "nodejs" to "npm".
+///
+/// These are meant for `DynamicVersionRange::from_str_with_aliases`.
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::schemes::default_scheme_aliases;
+///
+/// assert_eq!(default_scheme_aliases()["nodejs"], "npm");
+/// ```
+pub fn default_scheme_aliases() -> HashMap<String, String> {
+    [("node", "npm"), ("nodejs", "npm"), ("npmjs", "npm")]
+        .into_iter()
+        .map(|(alias, scheme)| (alias.to_string(), scheme.to_string()))
+        .collect()
+}
+
+/// Get the canonical name of a versioning scheme, mapping aliases of the same scheme to a
+/// single name.
+///
+/// Both "npm" and "semver" use SemVer versions and map to "semver".
+///
+/// # Arguments
+///
+/// * `scheme` - The versioning scheme (e.g., "npm", "semver")
+///
+/// # Returns
+///
+/// The canonical scheme name, or `None` if the scheme is unknown
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::schemes::canonical_scheme;
+///
+/// assert_eq!(canonical_scheme("npm"), Some("semver"));
+/// assert_eq!(canonical_scheme("pypi"), None);
+/// ```
+pub fn canonical_scheme(scheme: &str) -> Option<&'static str> {
+    match scheme.to_lowercase().as_str() {
+        "semver" | "npm" => Some("semver"),
+        "numeric" => Some("numeric"),
+        "conda" => Some("conda"),
+        _ => None,
+    }
+}
+
+/// Validate a version string against the rules of a versioning scheme.
+///
+/// This parses the version with the version type backing the given scheme,
+/// without constructing a version range.
+///
+/// # Arguments
+///
+/// * `scheme` - The versioning scheme (e.g., "npm", "semver")
+/// * `version` - The version string to validate
+///
+/// # Returns
+///
+/// `Ok(())` if the version is valid for the scheme, `VersError::InvalidVersionFormat` if it
+/// is not, or `VersError::UnsupportedVersioningScheme` if the scheme is unknown
+///
+/// # Examples
+///
+/// ```
+/// use vers_rs::schemes::validate;
+///
+/// assert!(validate("npm", "1.2.3").is_ok());
+/// assert!(validate("npm", "not-a-version").is_err());
+/// ```
+pub fn validate(scheme: &str, version: &str) -> Result<(), VersError> {
+    match scheme.to_lowercase().as_str() {
+        "semver" => version.parse::<SemVer>().map(|_| ()),
+        "npm" => SemVer::parse_partial(version).map(|_| ()),
+        "numeric" => version.parse::<NumericVersion>().map(|_| ()),
+        "conda" => version.parse::<CondaVersion>().map(|_| ()),
+        _ => Err(VersError::UnsupportedVersioningScheme(scheme.to_string())),
+    }
+}
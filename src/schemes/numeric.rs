@@ -0,0 +1,96 @@
+use crate::VersError;
+use crate::constraint::VT;
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub static NUMERIC_SCHEME: &str = "numeric";
+
+/// A version made of any number of dot-separated non-negative integers, e.g. `12.345.6789`.
+///
+/// Versions are compared segment by segment, with missing trailing segments treated as
+/// zero, so `1.2` is equal to `1.2.0`. Leading zeros of segments are dropped when parsing,
+/// so `01.002` is stored and displayed as `1.2`.
+#[derive(Clone, Debug)]
+pub struct NumericVersion(Vec<u64>);
+
+impl NumericVersion {
+    /// Get the segments of this version, as parsed.
+    pub fn segments(&self) -> &[u64] {
+        &self.0
+    }
+}
+
+impl Default for NumericVersion {
+    fn default() -> Self {
+        NumericVersion(vec![0])
+    }
+}
+
+impl PartialEq for NumericVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for NumericVersion {}
+
+impl PartialOrd for NumericVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NumericVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.0.len().max(other.0.len());
+        (0..len)
+            .map(|i| {
+                let a = self.0.get(i).copied().unwrap_or(0);
+                let b = other.0.get(i).copied().unwrap_or(0);
+                a.cmp(&b)
+            })
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl VT for NumericVersion {
+    const CANONICAL_SCHEMES: &'static [&'static str] = &[NUMERIC_SCHEME];
+
+    fn example() -> Self {
+        NumericVersion(vec![1, 2, 3])
+    }
+}
+
+impl Display for NumericVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for NumericVersion {
+    type Err = VersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments = s
+            .split('.')
+            .map(|segment| {
+                // Only plain digits are allowed, rejecting signs accepted by `u64::from_str`
+                if !segment.is_empty() && !segment.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(format!("invalid segment \"{}\"", segment));
+                }
+                segment.parse::<u64>().map_err(|e| format!("invalid segment \"{}\": {}", segment, e))
+            })
+            .collect::<Result<Vec<u64>, String>>()
+            .map_err(|e| VersError::InvalidVersionFormat(NUMERIC_SCHEME, s.to_string(), e))?;
+        Ok(NumericVersion(segments))
+    }
+}
@@ -0,0 +1,37 @@
+use crate::VersError;
+use crate::constraint::VT;
+use derive_more::Display;
+use std::str::FromStr;
+
+/// A version without any ordering, compared for equality only.
+///
+/// Ranges of opaque versions can only use the `=`, `!=` and `*` comparators. The `Ord`
+/// implementation (by string) only serves to sort constraints and carries no meaning.
+///
+/// This type is not tied to a versioning scheme, so it is not dispatched to by
+/// `DynamicVersionRange` and not listed by `supported_schemes`. Use it with
+/// `GenericVersionRange` for schemes whose versions are only compared for equality:
+///
+/// ```
+/// use vers_rs::GenericVersionRange;
+/// use vers_rs::range::VersionRange;
+/// use vers_rs::schemes::opaque::OpaqueVersion;
+///
+/// let range: GenericVersionRange<OpaqueVersion> = "vers:generic/abc|def".parse().unwrap();
+/// assert!(range.contains(&"abc".parse().unwrap()).unwrap());
+/// assert!(!range.contains(&"ghi".parse().unwrap()).unwrap());
+/// ```
+#[derive(Display, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct OpaqueVersion(String);
+
+impl VT for OpaqueVersion {
+    const HAS_ORDERING: bool = false;
+}
+
+impl FromStr for OpaqueVersion {
+    type Err = VersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(OpaqueVersion(s.to_string()))
+    }
+}
@@ -1,20 +1,306 @@
-use crate::VersError;
+use crate::{GenericVersionRange, VersError, VersionConstraint};
+use crate::comparator::Comparator::*;
+use crate::constraint::VT;
+use crate::range::interval;
 use derive_more::Display;
 use semver::Version;
 use std::cmp::Ordering;
+use std::ops::Bound::*;
 use std::str::FromStr;
 
 pub static SEMVER_SCHEME: &str = "semver/npm";
 
-#[derive(Display, Clone, Debug, PartialEq, Eq, PartialOrd)]
+/// A SemVer version, as used by the "semver" and "npm" schemes.
+///
+/// Partial versions are padded when parsing them in "npm" ranges (see `parse_partial`), so
+/// `1.0` is stored and displayed as `1.0.0`. `FromStr` and "semver" ranges require full
+/// versions.
+#[derive(Display, Clone, Debug, PartialEq, Eq)]
 pub struct SemVer(Version);
 
+impl SemVer {
+    /// Check whether this version has pre-release identifiers, e.g. `1.0.0-rc.1`.
+    pub fn is_prerelease(&self) -> bool {
+        !self.0.pre.is_empty()
+    }
+
+    /// Parse a possibly partial SemVer version, filling missing minor and patch numbers
+    /// with zero, so that `1` parses as `1.0.0` and `1.2` as `1.2.0`.
+    ///
+    /// Pre-release and build metadata are kept, e.g. `1.2-beta` parses as `1.2.0-beta`.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The version string to parse
+    ///
+    /// # Returns
+    ///
+    /// The parsed version, or `VersError::InvalidVersionFormat` if it is not valid
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// assert_eq!(SemVer::parse_partial("1.2").unwrap().to_string(), "1.2.0");
+    /// ```
+    pub fn parse_partial(s: &str) -> Result<SemVer, VersError> {
+        let error = match Version::parse(s) {
+            Ok(version) => return Ok(SemVer(version)),
+            Err(e) => VersError::InvalidVersionFormat(SEMVER_SCHEME, s.to_string(), e.to_string()),
+        };
+
+        let core_end = s.find(['-', '+']).unwrap_or(s.len());
+        let (core, suffix) = s.split_at(core_end);
+        let padding = match core.split('.').count() {
+            1 => ".0.0",
+            2 => ".0",
+            _ => return Err(error),
+        };
+        Version::parse(&format!("{}{}{}", core, padding, suffix))
+            .map(SemVer)
+            .map_err(|_| error)
+    }
+
+    /// Get the next patch release after this version, e.g. `1.2.4` for `1.2.3`.
+    ///
+    /// Pre-release and build metadata are dropped. The patch number saturates at its
+    /// maximum value.
+    pub fn increment_patch(&self) -> SemVer {
+        SemVer(Version::new(self.0.major, self.0.minor, self.0.patch.saturating_add(1)))
+    }
+
+    /// Get the smallest version above this version, ignoring build metadata, e.g. `1.2.4-0`
+    /// for `1.2.3` and `1.2.3-rc.1.0` for `1.2.3-rc.1`.
+    ///
+    /// A release is followed by the lowest pre-release of the next patch release, and a
+    /// pre-release by itself with an additional `0` identifier. Build metadata is dropped.
+    pub fn successor(&self) -> SemVer {
+        let mut version = Version::new(self.0.major, self.0.minor, self.0.patch);
+        let pre = if self.is_prerelease() {
+            format!("{}.0", self.0.pre)
+        } else {
+            version = match (version.major, version.minor, version.patch) {
+                (major, minor, patch) if patch < u64::MAX => Version::new(major, minor, patch + 1),
+                (major, minor, _) if minor < u64::MAX => Version::new(major, minor + 1, 0),
+                (major, _, _) => Version::new(major.saturating_add(1), 0, 0),
+            };
+            "0".to_string()
+        };
+        version.pre = semver::Prerelease::new(&pre).expect("valid pre-release identifiers");
+        SemVer(version)
+    }
+
+    /// Get the previous patch release of this version's release tuple, e.g. `1.2.2` for
+    /// `1.2.3`.
+    ///
+    /// Pre-release and build metadata are dropped, so the result of a pre-release precedes
+    /// its release, e.g. `1.2.2` for `1.2.3-beta`. Returns `None` if the patch number is `0`,
+    /// as the preceding release, e.g. `1.1.x` for `1.2.0`, has no finite patch number.
+    /// `increment_patch` undoes this for releases.
+    pub fn decrement(&self) -> Option<SemVer> {
+        let patch = self.0.patch.checked_sub(1)?;
+        Some(SemVer(Version::new(self.0.major, self.0.minor, patch)))
+    }
+}
+
+impl GenericVersionRange<SemVer> {
+    /// Get the constraint versions of this range that carry pre-release identifiers.
+    ///
+    /// Unless pre-releases are allowed, these are the versions whose release tuple admits
+    /// pre-releases into the range, which explains its behavior around pre-releases.
+    ///
+    /// # Returns
+    ///
+    /// The pre-release constraint versions, in constraint order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.0.0-rc1|<2.0.0".parse().unwrap();
+    /// assert_eq!(range.prerelease_boundaries()[0].to_string(), "1.0.0-rc1");
+    /// ```
+    pub fn prerelease_boundaries(&self) -> Vec<&SemVer> {
+        self.constraints
+            .iter()
+            .map(|c| &c.version)
+            .filter(|v| v.is_prerelease())
+            .collect()
+    }
+
+    /// Get the compact npm form of this range, if it matches the semantics of an npm caret
+    /// (`^`) or tilde (`~`) range exactly.
+    ///
+    /// `^1.2.3` stands for `>=1.2.3|<2.0.0`, `^0.2.3` for `>=0.2.3|<0.3.0` and `^0.0.3` for
+    /// `>=0.0.3|<0.0.4`, while `~1.2.3` stands for `>=1.2.3|<1.3.0`. When both forms match,
+    /// the caret form is returned. The range is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// The caret or tilde range, or `None` if the range has no such compact form
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.2.3|<2.0.0".parse().unwrap();
+    /// assert_eq!(range.to_npm_range(), Some("^1.2.3".to_string()));
+    /// ```
+    pub fn to_npm_range(&self) -> Option<String> {
+        let [lower, upper] = self.constraints.as_slice() else {
+            return None;
+        };
+        if lower.comparator != GreaterThanOrEqual || upper.comparator != LessThan {
+            return None;
+        }
+        let (from, to) = (&lower.version.0, &upper.version.0);
+        if !to.pre.is_empty() || !to.build.is_empty() {
+            return None;
+        }
+
+        if caret_upper(from).is_some_and(|caret| *to == caret) {
+            Some(format!("^{}", from))
+        } else if tilde_upper(from).is_some_and(|tilde| *to == tilde) {
+            Some(format!("~{}", from))
+        } else {
+            None
+        }
+    }
+
+    /// Get the smallest npm caret (`^`) or tilde (`~`) range containing every version of
+    /// this range.
+    ///
+    /// Both forms start at the lowest version of this range, so the one ending first is
+    /// the smallest. `!=` exclusions do not matter, as the compact form may contain more
+    /// versions than this range. The range is expected to be normalized.
+    ///
+    /// # Returns
+    ///
+    /// The caret or tilde range, or `None` if the range is empty, unbounded or too wide for
+    /// both forms
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range: GenericVersionRange<SemVer> = "vers:npm/>=1.2.3|<1.2.8".parse().unwrap();
+    /// assert_eq!(range.smallest_covering_caret(), Some("~1.2.3".to_string()));
+    /// ```
+    pub fn smallest_covering_caret(&self) -> Option<String> {
+        let intervals = interval::from_constraints(&self.constraints);
+        let span = (intervals.first()?.0.clone(), intervals.last()?.1.clone());
+        let from = match &span.0 {
+            Included(v) | Excluded(v) => v.0.clone(),
+            Unbounded => return None,
+        };
+
+        let covering = |upper: Option<Version>| {
+            let upper = upper?;
+            let candidate = (Included(SemVer(from.clone())), Excluded(SemVer(upper.clone())));
+            interval::covers(&[candidate], &span).then_some(upper)
+        };
+        match (covering(tilde_upper(&from)), covering(caret_upper(&from))) {
+            (Some(tilde), Some(caret)) if tilde < caret => Some(format!("~{}", from)),
+            (_, Some(_)) => Some(format!("^{}", from)),
+            (Some(_), None) => Some(format!("~{}", from)),
+            (None, None) => None,
+        }
+    }
+
+    /// Create a version range from an npm caret (`^`) or tilde (`~`) range, the inverse of
+    /// `to_npm_range`.
+    ///
+    /// Following npm, a caret allows changes that do not modify the left-most non-zero
+    /// number: `^1.2.3` is `>=1.2.3|<2.0.0`, `^0.2.3` is `>=0.2.3|<0.3.0` and `^0.0.3` is
+    /// `>=0.0.3|<0.0.4`. A tilde allows patch changes: `~1.2.3` is `>=1.2.3|<1.3.0`. A
+    /// pre-release only moves the lower bound, e.g. `^1.2.3-beta.4` is
+    /// `>=1.2.3-beta.4|<2.0.0`. Build metadata is ignored, as by npm, so `^1.2.3+build.1` is
+    /// `>=1.2.3|<2.0.0`. Partial versions such as `^1.2` are not supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `range` - The npm caret or tilde range
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing either the "npm" range or an error if the input is not a caret
+    /// or tilde range of a full SemVer version
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vers_rs::GenericVersionRange;
+    /// use vers_rs::schemes::semver::SemVer;
+    ///
+    /// let range = GenericVersionRange::<SemVer>::from_npm_range("^0.2.3").unwrap();
+    /// assert_eq!(range.to_string(), "vers:npm/>=0.2.3|<0.3.0");
+    /// ```
+    pub fn from_npm_range(range: &str) -> Result<Self, VersError> {
+        let range = range.trim();
+        let (upper_of, version): (fn(&Version) -> Option<Version>, &str) =
+            if let Some(version) = range.strip_prefix('^') {
+                (caret_upper, version)
+            } else if let Some(version) = range.strip_prefix('~') {
+                (tilde_upper, version)
+            } else {
+                return Err(VersError::InvalidConstraint(format!(
+                    "Expected an npm caret or tilde range: {}",
+                    range
+                )));
+            };
+
+        let mut from = Version::parse(version.trim()).map_err(|e| {
+            VersError::InvalidVersionFormat(SEMVER_SCHEME, version.to_string(), e.to_string())
+        })?;
+        from.build = semver::BuildMetadata::EMPTY;
+        let to = upper_of(&from).ok_or_else(|| {
+            VersError::InvalidConstraint(format!("No version above the npm range: {}", range))
+        })?;
+        Self::try_from_parts(
+            "npm".to_string(),
+            vec![
+                VersionConstraint::new(GreaterThanOrEqual, SemVer(from)),
+                VersionConstraint::new(LessThan, SemVer(to)),
+            ],
+        )
+    }
+}
+
+/// Get the exclusive upper bound of the npm caret range of a version, which increments
+/// its left-most non-zero number, or `None` if that number overflows.
+fn caret_upper(from: &Version) -> Option<Version> {
+    Some(match (from.major, from.minor) {
+        (0, 0) => Version::new(0, 0, from.patch.checked_add(1)?),
+        (0, minor) => Version::new(0, minor.checked_add(1)?, 0),
+        (major, _) => Version::new(major.checked_add(1)?, 0, 0),
+    })
+}
+
+/// Get the exclusive upper bound of the npm tilde range of a version, which increments its
+/// minor number, or `None` if it overflows.
+fn tilde_upper(from: &Version) -> Option<Version> {
+    Some(Version::new(from.major, from.minor.checked_add(1)?, 0))
+}
+
 impl Default for SemVer {
     fn default() -> Self {
         SemVer(Version::new(0, 0, 0))
     }
 }
 
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Ord for SemVer {
     fn cmp(&self, other: &Self) -> Ordering {
         self.0.cmp(&other.0)
@@ -56,14 +342,53 @@ impl Ord for SemVer {
     }
 }
 
+impl VT for SemVer {
+    const CANONICAL_SCHEMES: &'static [&'static str] = &["semver", "npm"];
+
+    fn is_prerelease(&self) -> bool {
+        SemVer::is_prerelease(self)
+    }
+
+    fn example() -> Self {
+        SemVer(Version::new(1, 2, 3))
+    }
+
+    fn to_release(&self) -> Self {
+        SemVer(Version::new(self.0.major, self.0.minor, self.0.patch))
+    }
+
+    /// Build metadata is ignored, so the next release of `1.0.0+a` is `1.0.1`.
+    fn next_release(&self) -> Option<Self> {
+        if self.is_prerelease() {
+            Some(self.to_release())
+        } else {
+            Some(SemVer(Version::new(self.0.major, self.0.minor, self.0.patch.checked_add(1)?)))
+        }
+    }
+
+    /// npm ranges commonly omit trailing segments, as in `>=1.2`, so versions of "npm"
+    /// ranges may be partial.
+    fn parse_in_scheme(s: &str, versioning_scheme: &str) -> Result<Self, VersError> {
+        if versioning_scheme == "npm" {
+            SemVer::parse_partial(s)
+        } else {
+            s.parse()
+        }
+    }
+
+    fn allows_prerelease(&self, prerelease: &Self) -> bool {
+        self.is_prerelease()
+            && (self.0.major, self.0.minor, self.0.patch)
+                == (prerelease.0.major, prerelease.0.minor, prerelease.0.patch)
+    }
+}
+
 impl FromStr for SemVer {
     type Err = VersError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(SemVer(Version::parse(s).map_err(|e| VersError::InvalidVersionFormat(
-            SEMVER_SCHEME,
-            s.to_string(),
-            e.to_string(),
-        ))?))
+        Version::parse(s)
+            .map(SemVer)
+            .map_err(|e| VersError::InvalidVersionFormat(SEMVER_SCHEME, s.to_string(), e.to_string()))
     }
 }
\ No newline at end of file